@@ -2,17 +2,6 @@
 extern crate Lattice;
 use Lattice::window::{Window};
 use Lattice::view::{View, Text};
-use std::rc::Rc;
-use std::cell::{RefCell,Cell};
-use std::sync::Mutex;
-
-#[macro_use]
-extern crate lazy_static;
-
-lazy_static! {
-    static ref text_clicked: Mutex<Cell<bool>> = Mutex::new(Cell::new(false));
-    static ref left_hovered: Mutex<Cell<bool>> = Mutex::new(Cell::new(false));
-}
 
 fn main() {
     let mut w = Window::new("Premadeath").set_fullscreen(true);
@@ -21,29 +10,32 @@ fn main() {
     w.start(|events| {
        let mut v = View::new();
 
-       let lh = left_hovered.lock().unwrap();
-       let tc = text_clicked.lock().unwrap();
+       let lh = events.is_hovered("hover text");
+       let tc = events.get("text clicked") == "true";
 
        v.append(Text::new("assets/Macondo-Regular.ttf", "hover text")
-               .shadow((if lh.get() {[-3,-3,3,3]} else {[0,0,0,0]}),
-                       (if lh.get() {[0.8,0.8,0.8,0.8]} else {[0.0, 0.0, 0.0, 0.0]}))
-               .hovered(move |e| {
-                  let lh = left_hovered.lock().unwrap().set(true);
+               .shadow((if lh {[-3,-3,3,3]} else {[0,0,0,0]}),
+                       (if lh {[0.8,0.8,0.8,0.8]} else {[0.0, 0.0, 0.0, 0.0]}))
+               .hoverable("hover text")
+               .mouse_enter(|e, _| {
+                  let count: usize = e.get("hover count").parse().unwrap_or(0);
+                  e.set("hover count", (count + 1).to_string().as_str());
                })
                .color([0.4, 0.4, 1.0, 1.0])
                .scale(2.0, "em")
                .width(25.0, "%")
                .translate_x(150.0, "px")
                .translate_y(150.0, "px"));
-       lh.set(false);
+
+       v.append(Text::new("assets/Macondo-Regular.ttf", format!("hovered {} times", events.get("hover count").parse::<usize>().unwrap_or(0)).as_str())
+               .color([0.8, 0.8, 0.8, 1.0])
+               .translate_x(150.0, "px")
+               .translate_y(220.0, "px"));
 
        v.append(Text::new("assets/Macondo-Regular.ttf", "click text")
-               .shadow((if tc.get() {[-3,-3,3,3]} else {[0,0,0,0]}),
-                       (if tc.get() {[0.8,0.8,0.8,1.0]} else {[0.0,0.0,0.0,0.0]}))
-               .clicked(move |e| {
-                  let tc = text_clicked.lock().unwrap();
-                  tc.set(true);
-               })
+               .shadow((if tc {[-3,-3,3,3]} else {[0,0,0,0]}),
+                       (if tc {[0.8,0.8,0.8,1.0]} else {[0.0,0.0,0.0,0.0]}))
+               .clicked(|e| e.set("text clicked", "true"))
                .color([1.0, 0.4, 0.4, 1.0])
                .scale(3.0, "em")
                .width(40.0, "%")
@@ -0,0 +1,33 @@
+#[macro_use(with_assets)]
+extern crate Lattice;
+use Lattice::window::{Window};
+use Lattice::view::{View, Group, Text, Rectangle};
+
+fn main() {
+    let mut w = Window::new("Premadeath")
+              .set_fullscreen(true);
+    with_assets!(w);
+    w.start(|_| {
+       let mut v = View::new();
+       v.append(Group::column()
+               .width(100.0, "%")
+               .height(100.0, "%")
+               .append(Group::row()
+                       .height(10.0, "%")
+                       .gap(1.0, "em")
+                       .justify("space-between")
+                       .align_items("center")
+                       .append(Text::new("assets/Macondo-Regular.ttf", "File"))
+                       .append(Text::new("assets/Macondo-Regular.ttf", "Edit"))
+                       .append(Text::new("assets/Macondo-Regular.ttf", "Help")))
+               .append(Group::row()
+                       .grow(1.0)
+                       .gap(1.0, "em")
+                       .append(Rectangle::new(20.0, "%", 100.0, "%")
+                               .color([0.2, 0.2, 0.4, 1.0]))
+                       .append(Rectangle::new(0.0, "px", 100.0, "%")
+                               .color([0.4, 0.4, 1.0, 1.0])
+                               .grow(1.0))));
+       v
+    });
+}
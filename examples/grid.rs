@@ -0,0 +1,42 @@
+#[macro_use(with_assets)]
+extern crate Lattice;
+use Lattice::window::{Window};
+use Lattice::view::{View, Group, Text, Rectangle};
+
+fn main() {
+    let mut w = Window::new("Premadeath")
+              .set_fullscreen(true)
+              .set_capture_key("F12", "screenshots/examples_grid.png");
+    with_assets!(w);
+    w.start(|_| {
+       let mut v = View::new();
+
+       //Different colors for different tiles
+       let colors = [
+          [1.0, 0.4, 0.4, 1.0],
+          [0.4, 1.0, 0.4, 1.0],
+          [0.4, 0.4, 1.0, 1.0],
+          [1.0, 1.0, 1.0, 1.0]
+       ];
+
+       //The same 10x10 tiles as modifier_stacking, without computing any offsets by hand
+       let mut inventory = Group::grid()
+              .width(75.0, "<%")
+              .height(75.0, "<%")
+              .translate_x(0.0, "=")
+              .translate_y(0.0, "=")
+              .gap(2.0, "px")
+              .columns(&[(1.0, "fr"); 10])
+              .append(Text::new("assets/Macondo-Regular.ttf", "Inventory")
+                      .scale(2.0, "em")
+                      .align("center")
+                      .span(10, 1));
+       for i in 0..90 {
+          inventory = inventory.append(Rectangle::new(100.0, "%", 100.0, "%")
+                                       .color( colors[(i % 4) as usize] ));
+       }
+       v.append(inventory);
+
+       v
+    });
+}
@@ -0,0 +1,41 @@
+#[macro_use(with_assets)]
+extern crate Lattice;
+use Lattice::window::{Window};
+use Lattice::view::{View, Component, Group, Text, Rectangle};
+
+//A reusable panel: every position inside is relative to the panel's own box
+fn panel(title: &str) -> Component {
+   Group::new()
+      .width(30.0, "%")
+      .height(30.0, "%")
+      .border([1.0, 1.0, 1.0, 1.0], 2.0, "px")
+      .append(Rectangle::new(100.0, "%", 100.0, "%")
+              .color([0.2, 0.2, 0.4, 1.0]))
+      .append(Text::new("assets/Macondo-Regular.ttf", title)
+              .scale(2.0, "em")
+              .width(100.0, "%")
+              .align("center")
+              .translate_y(10.0, "%"))
+      .append(Rectangle::new(80.0, "%", 20.0, "%")
+              .color([0.4, 0.4, 1.0, 1.0])
+              .translate_x(0.0, "=")
+              .translate_y(60.0, "%"))
+}
+
+fn main() {
+    let mut w = Window::new("Premadeath")
+              .set_fullscreen(true);
+    with_assets!(w);
+    w.start(|events| {
+       let mut v = View::new();
+       v.append(panel("left")
+               .translate_x(10.0, "%")
+               .translate_y(10.0, "%"));
+       v.append(panel("spinning")
+               .cog(0.5, 0.5)
+               .rotate(events.time_elapsed * 30.0, "degree")
+               .translate_x(65.0, "%")
+               .translate_y(60.0, "%"));
+       v
+    });
+}
@@ -0,0 +1,21 @@
+#[macro_use(with_assets)]
+extern crate Lattice;
+use Lattice::window::{Window};
+use Lattice::view::{View, Image, Text};
+
+fn main() {
+    let mut w = Window::new("Premadeath");
+    with_assets!(w);
+    let mut h = w.headless(800, 600);
+    let frame = h.render(1.0, |events| {
+       let mut v = View::new();
+       v.append(Image::new("assets/background.png")
+                  .height(100.0, "%")
+                  .width(100.0, "%"));
+       v.append(Text::new("assets/Macondo-Regular.ttf",
+                format!("{:.2} seconds since start", events.time_elapsed).as_str())
+               .scale(3.0, "em"));
+       v
+    });
+    frame.save("headless.png").expect("write headless.png");
+}
@@ -0,0 +1,46 @@
+#[macro_use(with_assets)]
+extern crate Lattice;
+use Lattice::window::{Window};
+use Lattice::view::{View, Text, Rectangle};
+
+fn main() {
+    let mut w = Window::new("Premadeath");
+    with_assets!(w);
+
+    w.start(|events| {
+       let x: f64 = events.get("x").parse().unwrap_or(50.0);
+       let y: f64 = events.get("y").parse().unwrap_or(50.0);
+
+       let mut v = View::new();
+       v.key_down(|e, d| {
+          let key = d.key.clone().expect("key");
+          let step = if key.shift { 10.0 } else { 2.0 };
+          let (dx, dy) = match key.name.as_str() {
+             "Left" => { (-step, 0.0) }
+             "Right" => { (step, 0.0) }
+             "Up" => { (0.0, -step) }
+             "Down" => { (0.0, step) }
+             _ => { (0.0, 0.0) }
+          };
+          let x: f64 = e.get("x").parse().unwrap_or(50.0);
+          let y: f64 = e.get("y").parse().unwrap_or(50.0);
+          e.set("x", (x + dx).max(0.0).min(95.0).to_string().as_str());
+          e.set("y", (y + dy).max(0.0).min(95.0).to_string().as_str());
+       });
+       v.key_repeat(|e, d| {
+          let key = d.key.clone().expect("key");
+          e.set("held", key.name.as_str());
+       });
+       v.key_up(|e, _| e.set("held", ""));
+
+       v.append(Text::new("assets/Macondo-Regular.ttf", "move with the arrow keys, hold shift to go faster")
+               .color([0.8, 0.8, 0.8, 1.0])
+               .translate_x(10.0, "px")
+               .translate_y(10.0, "px"));
+       v.append(Rectangle::new(5.0, "%", 5.0, "%")
+               .color(if events.get("held").is_empty() { [0.4, 0.4, 1.0, 1.0] } else { [1.0, 0.4, 0.4, 1.0] })
+               .translate_x(x, "%")
+               .translate_y(y, "%"));
+       v
+    });
+}
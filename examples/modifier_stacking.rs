@@ -5,7 +5,8 @@ use Lattice::view::{View, Image, Rectangle};
 
 fn main() {
     let mut w = Window::new("Premadeath")
-              .set_fullscreen(true);
+              .set_fullscreen(true)
+              .set_capture_key("F12", "screenshots/examples_modifier_stacking.png");
     with_assets!(w);
     w.start(|events| {
        let mut v = View::new();
@@ -0,0 +1,69 @@
+#[macro_use(with_assets)]
+extern crate Lattice;
+use Lattice::window::{Window};
+use Lattice::view::{View, Text, Rectangle};
+use Lattice::events::{Button};
+
+fn main() {
+    let mut w = Window::new("Premadeath");
+    with_assets!(w);
+
+    w.start(|events| {
+       let x: f64 = events.get("x").parse().unwrap_or(100.0);
+       let y: f64 = events.get("y").parse().unwrap_or(100.0);
+       let size: f64 = events.get("size").parse().unwrap_or(80.0);
+
+       let mut v = View::new();
+       v.append(Text::new("assets/Macondo-Regular.ttf", "drag the square, scroll to resize it, double click to reset, right click for a menu")
+               .color([0.8, 0.8, 0.8, 1.0])
+               .translate_x(10.0, "px")
+               .translate_y(10.0, "px"));
+       v.append(Rectangle::new(size, "px", size, "px")
+               .color(if events.get("dragging").is_empty() { [0.4, 0.4, 1.0, 1.0] } else { [1.0, 0.4, 0.4, 1.0] })
+               .translate_x(x, "px")
+               .translate_y(y, "px")
+               .drag_start(|e, _| e.set("dragging", "1"))
+               .drag_move(|e, d| {
+                  let mouse = d.mouse.clone().expect("mouse");
+                  let x: f64 = e.get("x").parse().unwrap_or(100.0);
+                  let y: f64 = e.get("y").parse().unwrap_or(100.0);
+                  e.set("x", (x + mouse.delta.0).to_string().as_str());
+                  e.set("y", (y + mouse.delta.1).to_string().as_str());
+               })
+               .drag_end(|e, _| e.set("dragging", ""))
+               .wheel(|e, d| {
+                  let mouse = d.mouse.clone().expect("mouse");
+                  let size: f64 = e.get("size").parse().unwrap_or(80.0);
+                  e.set("size", (size + mouse.delta.1 * 5.0).max(20.0).min(400.0).to_string().as_str());
+               })
+               .double_clicked(|e, _| {
+                  e.set("x", "100");
+                  e.set("y", "100");
+                  e.set("size", "80");
+               })
+               .mouse_down(|e, d| {
+                  let mouse = d.mouse.clone().expect("mouse");
+                  if mouse.button == Some(Button::Right) {
+                     e.set("menu", format!("{} {}", mouse.x, mouse.y).as_str());
+                  }
+               }));
+
+       let menu = events.get("menu");
+       if !menu.is_empty() {
+          let at: Vec<f64> = menu.split(' ').map(|s| s.parse().unwrap_or(0.0)).collect();
+          v.append(Rectangle::new(120.0, "px", 30.0, "px")
+                  .color([0.2, 0.2, 0.2, 1.0])
+                  .translate_x(at[0], "px")
+                  .translate_y(at[1], "px")
+                  .clicked(|e| {
+                     e.set("size", "80");
+                     e.set("menu", "");
+                  }));
+          v.append(Text::new("assets/Macondo-Regular.ttf", "reset size")
+                  .color([0.9, 0.9, 0.9, 1.0])
+                  .translate_x(at[0] + 8.0, "px")
+                  .translate_y(at[1] + 4.0, "px"));
+       }
+       v
+    });
+}
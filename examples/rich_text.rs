@@ -0,0 +1,36 @@
+#[macro_use(with_assets)]
+extern crate Lattice;
+use Lattice::window::{Window};
+use Lattice::view::{View, RichText, TextSpan};
+
+fn main() {
+    let mut w = Window::new("Premadeath")
+              .set_fullscreen(true)
+              .set_capture_key("F12", "screenshots/examples_rich_text.png");
+    with_assets!(w);
+    w.start(|_| {
+       let mut v = View::new();
+
+       //A dialogue box highlighting names and keywords inline
+       v.append(RichText::new("assets/Macondo-Regular.ttf")
+               .scale(1.5, "em")
+               .width(60.0, "%")
+               .padding(1.0, "em")
+               .translate_x(0.0, "=")
+               .translate_y(60.0, "%")
+               .color([0.9, 0.9, 0.9, 1.0])
+               .append_span(TextSpan::new("Innkeeper: ")
+                            .color([1.0, 0.8, 0.2, 1.0]))
+               .append_span(TextSpan::new("The "))
+               .append_span(TextSpan::new("silver key")
+                            .color([0.4, 0.8, 1.0, 1.0])
+                            .underline())
+               .append_span(TextSpan::new(" opens the cellar. Bring it back before "))
+               .append_span(TextSpan::new("noon")
+                            .strikethrough())
+               .append_span(TextSpan::new(" midnight!")
+                            .scale(2.0, "em")));
+
+       v
+    });
+}
@@ -5,7 +5,8 @@ use Lattice::view::{View, Text};
 
 fn main() {
     let mut w = Window::new("Premadeath")
-              .set_fullscreen(true);
+              .set_fullscreen(true)
+              .set_capture_key("F12", "screenshots/examples_scalar_units.png");
     with_assets!(w);
     w.start(|events| {
        let mut v = View::new();
@@ -58,6 +59,12 @@ fn main() {
                .translate_x(10.0, "px")
                .translate_y(22.0, "em"));
 
+       v.append(Text::new("assets/Macondo-Regular.ttf", "calc(10% - 2em)")
+               .scale(2.0, "em")
+               .color([1.0, 1.0, 1.0, 1.0])
+               .translate_x(1.0, "calc(10% - 2em)")
+               .translate_y(25.0, "em"));
+
        v
     });
 }
@@ -5,7 +5,8 @@ use Lattice::view::{View, Text};
 
 fn main() {
     let mut w = Window::new("Premadeath")
-              .set_fullscreen(true);
+              .set_fullscreen(true)
+              .set_capture_key("F12", "screenshots/examples_text.png");
     with_assets!(w);
     w.start(|events| {
        let mut v = View::new();
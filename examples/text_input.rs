@@ -0,0 +1,48 @@
+#[macro_use(with_assets)]
+extern crate Lattice;
+use Lattice::window::{Window};
+use Lattice::view::{View, Text, TextInput};
+
+fn main() {
+    let mut w = Window::new("Premadeath")
+              .set_fullscreen(true)
+              .set_capture_key("F12", "screenshots/examples_text_input.png");
+    with_assets!(w);
+    w.start(|events| {
+       let mut v = View::new();
+
+       //A login form: click a field to type into it
+       v.append(TextInput::new("assets/Macondo-Regular.ttf", "user")
+               .scale(1.5, "em")
+               .width(40.0, "%")
+               .padding(0.5, "em")
+               .border([0.2, 0.2, 0.3, 1.0], 2.0, "px")
+               .translate_x(0.0, "=")
+               .translate_y(30.0, "%"));
+       v.append(TextInput::new("assets/Macondo-Regular.ttf", "password")
+               .password('*')
+               .scale(1.5, "em")
+               .width(40.0, "%")
+               .padding(0.5, "em")
+               .border([0.2, 0.2, 0.3, 1.0], 2.0, "px")
+               .translate_x(0.0, "=")
+               .translate_y(40.0, "%"));
+
+       //Notes keep their line breaks
+       v.append(TextInput::new("assets/Macondo-Regular.ttf", "notes")
+               .multiline()
+               .width(40.0, "%")
+               .height(20.0, "%")
+               .padding(0.5, "em")
+               .border([0.2, 0.2, 0.3, 1.0], 2.0, "px")
+               .translate_x(0.0, "=")
+               .translate_y(50.0, "%"));
+
+       let greeting = format!("Welcome, {}", events.get("user"));
+       v.append(Text::new("assets/Macondo-Regular.ttf", greeting.as_str())
+               .translate_x(0.0, "=")
+               .translate_y(75.0, "%"));
+
+       v
+    });
+}
@@ -0,0 +1,220 @@
+extern crate image;
+use self::image::{RgbaImage, Rgba};
+use std::collections::{HashMap};
+
+///Key of a rasterized glyph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GlyphKey {
+   ///index of the font among the loaded assets
+   pub font: usize,
+
+   ///glyph id within the font
+   pub glyph: u32,
+
+   ///line height in pixels
+   pub size: usize,
+}
+
+///A glyph rasterized to an alpha coverage map, shared by every Backend
+pub struct Glyph {
+   ///bitmap width in pixels
+   pub width: usize,
+
+   ///line height in pixels
+   pub height: usize,
+
+   ///row-major alpha coverage, width * height bytes
+   pub coverage: Vec<u8>,
+}
+
+///A destination rectangle in pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+   ///left edge
+   pub x: i32,
+
+   ///top edge
+   pub y: i32,
+
+   ///width
+   pub w: u32,
+
+   ///height
+   pub h: u32,
+}
+impl Rect {
+   ///Create a new Rect
+   pub fn new(x: i32, y: i32, w: u32, h: u32) -> Rect {
+      Rect { x:x, y:y, w:w, h:h }
+   }
+}
+
+///A clockwise rotation about a pivot point in pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rotation {
+   ///angle in radians
+   pub radians: f64,
+
+   ///horizontal pivot
+   pub x: f64,
+
+   ///vertical pivot
+   pub y: f64,
+}
+impl Rotation {
+   ///Create a new Rotation
+   pub fn new(radians: f64, x: f64, y: f64) -> Rotation {
+      Rotation { radians:radians, x:x, y:y }
+   }
+   ///Rotate a point about the pivot
+   pub fn rotate(&self, x: f64, y: f64) -> (f64, f64) {
+      let (sin, cos) = self.radians.sin_cos();
+      let (dx, dy) = (x - self.x, y - self.y);
+      (self.x + dx*cos - dy*sin, self.y + dx*sin + dy*cos)
+   }
+   ///Undo the rotation of a point about the pivot
+   pub fn unrotate(&self, x: f64, y: f64) -> (f64, f64) {
+      let (sin, cos) = self.radians.sin_cos();
+      let (dx, dy) = (x - self.x, y - self.y);
+      (self.x + dx*cos + dy*sin, self.y - dx*sin + dy*cos)
+   }
+}
+
+///A render target. The render cycle resolves modifiers and lays out text, then issues these calls for every frame.
+///All drawing alpha blends onto what is already there.
+pub trait Backend {
+   ///Size of the drawable area in pixels
+   fn size(&mut self) -> (u32, u32);
+
+   ///Called once for every image asset before the first frame
+   fn load_image(&mut self, name: &str, image: &RgbaImage);
+
+   ///Fill the whole target with a color, starting a new frame
+   fn clear(&mut self, rgba: [f64; 4]);
+
+   ///Draw a solid rectangle, rotated after placement
+   fn draw_rect(&mut self, rect: Rect, rotation: Rotation, rgba: [f64; 4]);
+
+   ///Draw a loaded image, stretched to the destination rectangle and rotated after placement
+   fn draw_texture(&mut self, name: &str, rect: Rect, rotation: Rotation);
+
+   ///Draw a glyph with its top left corner at x,y, rotated after placement and tinted with a color.
+   ///The key identifies the glyph so that it can be cached.
+   fn draw_glyph(&mut self, key: GlyphKey, glyph: &Glyph, x: i32, y: i32, rotation: Rotation, rgba: [f64; 4]);
+
+   ///Called when the render cycle drops a glyph from its cache, so anything cached for the key can be released
+   fn evict_glyph(&mut self, _key: GlyphKey) {}
+
+   ///Read back everything drawn since the last clear, if the Backend can
+   fn capture(&mut self) -> Option<RgbaImage> { None }
+
+   ///Finish the frame
+   fn present(&mut self);
+}
+
+///A software rasterizer drawing into an in-memory RGBA buffer
+pub struct Software {
+   frame: RgbaImage,
+   images: HashMap<String,RgbaImage>,
+}
+
+impl Software {
+   ///Create a new Software Backend of a fixed size
+   pub fn new(width: u32, height: u32) -> Software {
+      Software {
+         frame: RgbaImage::new(width, height),
+         images: HashMap::new(),
+      }
+   }
+   ///The most recently drawn frame
+   pub fn frame(&self) -> &RgbaImage {
+      &self.frame
+   }
+}
+
+fn byte_color(rgba: [f64; 4]) -> [u8; 4] {
+   [(rgba[0]*255.0) as u8, (rgba[1]*255.0) as u8, (rgba[2]*255.0) as u8, (rgba[3]*255.0) as u8]
+}
+
+///Blends like SDL's BlendMode::Blend: dstRGB = srcRGB*srcA + dstRGB*(1-srcA), dstA = srcA + dstA*(1-srcA)
+fn blend(frame: &mut RgbaImage, x: i32, y: i32, src: [u8; 4]) {
+   let (width, height) = frame.dimensions();
+   if x < 0 || y < 0 || x >= width as i32 || y >= height as i32 { return }
+   let a = src[3] as u32;
+   let dst = frame.get_pixel_mut(x as u32, y as u32);
+   for i in 0..3 {
+      dst.data[i] = ((src[i] as u32 * a + dst.data[i] as u32 * (255 - a)) / 255) as u8;
+   }
+   dst.data[3] = (a + (dst.data[3] as u32 * (255 - a)) / 255) as u8;
+}
+
+///Blend every pixel of the frame whose center falls inside the rotated rectangle.
+///The sample function receives pixel coordinates relative to the unrotated rectangle.
+fn fill<S>(frame: &mut RgbaImage, rect: Rect, rotation: Rotation, mut sample: S)
+    where S: FnMut(u32, u32) -> [u8; 4] {
+   if rect.w == 0 || rect.h == 0 { return }
+   let (left, top) = (rect.x as f64, rect.y as f64);
+   let (right, bottom) = (left + rect.w as f64, top + rect.h as f64);
+   let corners = [rotation.rotate(left, top), rotation.rotate(right, top),
+                  rotation.rotate(left, bottom), rotation.rotate(right, bottom)];
+   let (width, height) = frame.dimensions();
+   let min_x = corners.iter().fold(right, |m, c| m.min(c.0)).floor().max(0.0) as i32;
+   let min_y = corners.iter().fold(bottom, |m, c| m.min(c.1)).floor().max(0.0) as i32;
+   let max_x = corners.iter().fold(left, |m, c| m.max(c.0)).ceil().min(width as f64) as i32;
+   let max_y = corners.iter().fold(top, |m, c| m.max(c.1)).ceil().min(height as f64) as i32;
+   for y in min_y..max_y {
+      for x in min_x..max_x {
+         let (u, v) = rotation.unrotate(x as f64 + 0.5, y as f64 + 0.5);
+         let (u, v) = (u - left, v - top);
+         if u >= 0.0 && v >= 0.0 && u < rect.w as f64 && v < rect.h as f64 {
+            let p = sample(u as u32, v as u32);
+            blend(frame, x, y, p);
+         }
+      }
+   }
+}
+
+impl Backend for Software {
+   fn size(&mut self) -> (u32, u32) {
+      self.frame.dimensions()
+   }
+   fn load_image(&mut self, name: &str, image: &RgbaImage) {
+      self.images.insert(name.to_string(), image.clone());
+   }
+   fn clear(&mut self, rgba: [f64; 4]) {
+      let c = byte_color(rgba);
+      for p in self.frame.pixels_mut() {
+         *p = Rgba { data: c };
+      }
+   }
+   fn draw_rect(&mut self, rect: Rect, rotation: Rotation, rgba: [f64; 4]) {
+      let c = byte_color(rgba);
+      fill(&mut self.frame, rect, rotation, |_, _| c);
+   }
+   fn draw_texture(&mut self, name: &str, rect: Rect, rotation: Rotation) {
+      //an image that was never loaded draws nothing
+      let image = match self.images.get(name) {
+         Some(image) => { image }
+         None => { return }
+      };
+      let (sw, sh) = image.dimensions();
+      fill(&mut self.frame, rect, rotation, |dx, dy| {
+         //nearest neighbour, sampled at the center of the destination pixel like SDL
+         let sx = ((2 * dx + 1) as u64 * sw as u64 / (2 * rect.w as u64)) as u32;
+         let sy = ((2 * dy + 1) as u64 * sh as u64 / (2 * rect.h as u64)) as u32;
+         image.get_pixel(sx, sy).data
+      });
+   }
+   fn draw_glyph(&mut self, _key: GlyphKey, glyph: &Glyph, x: i32, y: i32, rotation: Rotation, rgba: [f64; 4]) {
+      let c = byte_color(rgba);
+      let rect = Rect::new(x, y, glyph.width as u32, glyph.height as u32);
+      fill(&mut self.frame, rect, rotation, |gx, gy| {
+         let coverage = glyph.coverage[(gx as usize) + (gy as usize) * glyph.width] as u32;
+         [c[0], c[1], c[2], (coverage * c[3] as u32 / 255) as u8]
+      });
+   }
+   fn capture(&mut self) -> Option<RgbaImage> {
+      Some(self.frame.clone())
+   }
+   fn present(&mut self) {}
+}
@@ -0,0 +1,321 @@
+///The bidirectional character types of Unicode Standard Annex #9 that the implicit rules below tell apart.
+///Explicit embeddings, overrides and isolates are not supported and their formatting characters are ignored like BN.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Class {
+   L, R, AL, EN, ES, ET, AN, CS, NSM, BN, B, S, WS, ON,
+}
+
+fn class(c: char) -> Class {
+   match c {
+      '\n' | '\r' | '\u{1C}'..='\u{1E}' | '\u{85}' | '\u{2029}' => { Class::B }
+      '\t' | '\u{0B}' | '\u{1F}' => { Class::S }
+      ' ' | '\u{0C}' | '\u{1680}' | '\u{2000}'..='\u{200A}' | '\u{2028}' | '\u{205F}' | '\u{3000}' => { Class::WS }
+      '\u{00}'..='\u{08}' | '\u{0E}'..='\u{1B}' | '\u{7F}'..='\u{84}' | '\u{86}'..='\u{9F}' | '\u{AD}' |
+      '\u{200B}'..='\u{200D}' | '\u{202A}'..='\u{202E}' | '\u{2060}'..='\u{2069}' | '\u{FEFF}' => { Class::BN }
+      '\u{200E}' => { Class::L }
+      '\u{200F}' => { Class::R }
+      '\u{061C}' => { Class::AL }
+      '0'..='9' | '\u{B2}' | '\u{B3}' | '\u{B9}' | '\u{06F0}'..='\u{06F9}' | '\u{2070}'..='\u{2079}' |
+      '\u{2080}'..='\u{2089}' | '\u{FF10}'..='\u{FF19}' => { Class::EN }
+      '+' | '-' | '\u{207A}' | '\u{207B}' | '\u{208A}' | '\u{208B}' | '\u{FB29}' | '\u{FE62}' | '\u{FE63}' |
+      '\u{FF0B}' | '\u{FF0D}' => { Class::ES }
+      '#' | '$' | '%' | '\u{A2}'..='\u{A5}' | '\u{B0}' | '\u{B1}' | '\u{066A}' | '\u{2030}'..='\u{2034}' |
+      '\u{20A0}'..='\u{20CF}' | '\u{FE5F}' | '\u{FE69}' | '\u{FE6A}' | '\u{FF03}'..='\u{FF05}' |
+      '\u{FFE0}' | '\u{FFE1}' | '\u{FFE5}' | '\u{FFE6}' => { Class::ET }
+      '\u{0600}'..='\u{0605}' | '\u{0660}'..='\u{0669}' | '\u{066B}' | '\u{066C}' | '\u{06DD}' => { Class::AN }
+      ',' | '.' | '/' | ':' | '\u{A0}' | '\u{060C}' | '\u{202F}' | '\u{2044}' | '\u{FE50}' | '\u{FE52}' | '\u{FE55}' |
+      '\u{FF0C}' | '\u{FF0E}' | '\u{FF0F}' | '\u{FF1A}' => { Class::CS }
+      '\u{0300}'..='\u{036F}' | '\u{0483}'..='\u{0489}' | '\u{0591}'..='\u{05BD}' | '\u{05BF}' | '\u{05C1}' | '\u{05C2}' |
+      '\u{05C4}' | '\u{05C5}' | '\u{05C7}' | '\u{0610}'..='\u{061A}' | '\u{064B}'..='\u{065F}' | '\u{0670}' |
+      '\u{06D6}'..='\u{06DC}' | '\u{06DF}'..='\u{06E4}' | '\u{06E7}' | '\u{06E8}' | '\u{06EA}'..='\u{06ED}' |
+      '\u{1AB0}'..='\u{1AFF}' | '\u{1DC0}'..='\u{1DFF}' | '\u{20D0}'..='\u{20FF}' | '\u{FE00}'..='\u{FE0F}' |
+      '\u{FE20}'..='\u{FE2F}' => { Class::NSM }
+      '\u{0590}'..='\u{05FF}' | '\u{07C0}'..='\u{085F}' | '\u{FB1D}'..='\u{FB4F}' |
+      '\u{10800}'..='\u{10FFF}' | '\u{1E800}'..='\u{1EFFF}' => { Class::R }
+      '\u{0600}'..='\u{07BF}' | '\u{0860}'..='\u{08FF}' | '\u{FB50}'..='\u{FDCF}' | '\u{FDF0}'..='\u{FDFF}' |
+      '\u{FE70}'..='\u{FEFF}' => { Class::AL }
+      '!' | '"' | '&' | '\'' | '(' | ')' | '*' | ';' | '<' | '=' | '>' | '?' | '@' | '['..='`' | '{'..='~' |
+      '\u{A1}' | '\u{A6}'..='\u{A9}' | '\u{AB}' | '\u{AC}' | '\u{AE}' | '\u{AF}' | '\u{B4}' | '\u{B6}'..='\u{B8}' |
+      '\u{BB}'..='\u{BF}' | '\u{D7}' | '\u{F7}' | '\u{2010}'..='\u{2027}' | '\u{2035}'..='\u{2043}' |
+      '\u{2045}'..='\u{205E}' | '\u{2190}'..='\u{2BFF}' | '\u{3001}'..='\u{3003}' | '\u{3008}'..='\u{3020}' |
+      '\u{FE10}'..='\u{FE19}' | '\u{FE30}'..='\u{FE4F}' | '\u{FF01}' | '\u{FF02}' | '\u{FF06}'..='\u{FF0A}' |
+      '\u{FF1B}'..='\u{FF20}' | '\u{FF3B}'..='\u{FF40}' | '\u{FF5B}'..='\u{FF65}' | '\u{1F000}'..='\u{1FAFF}' => { Class::ON }
+      _ => { Class::L }
+   }
+}
+
+///The embedding level of the paragraph every character is in: 1 if its first strong character is right-to-left, otherwise 0.
+///A direction that is given applies to every paragraph instead.
+pub fn paragraphs(cs: &[char], direction: Option<u8>) -> Vec<u8> {
+   let mut out = Vec::with_capacity(cs.len());
+   let mut start = 0;
+   for i in 0..cs.len() {
+      if class(cs[i]) == Class::B || i + 1 == cs.len() {
+         let level = direction.unwrap_or_else(|| {
+            cs[start..i+1].iter().filter_map(|&c| match class(c) {
+               Class::L => { Some(0) }
+               Class::R | Class::AL => { Some(1) }
+               _ => { None }
+            }).next().unwrap_or(0)
+         });
+         for _ in start..i+1 { out.push(level); }
+         start = i + 1;
+      }
+   }
+   out
+}
+
+///Resolve the embedding level of every character with the weak, neutral and implicit rules of Unicode Standard Annex #9,
+///given the levels of their paragraphs. Odd levels run right-to-left.
+pub fn levels(cs: &[char], paragraphs: &[u8]) -> Vec<u8> {
+   let mut out = Vec::with_capacity(cs.len());
+   let mut start = 0;
+   for i in 0..cs.len() {
+      if i + 1 == cs.len() || class(cs[i]) == Class::B {
+         out.extend(paragraph(&cs[start..i+1], paragraphs[i]));
+         start = i + 1;
+      }
+   }
+   out
+}
+
+fn paragraph(cs: &[char], level: u8) -> Vec<u8> {
+   let e = if level % 2 == 0 { Class::L } else { Class::R };
+   let raw: Vec<Class> = cs.iter().map(|&c| class(c)).collect();
+   //X9: formatting characters take no part in resolution, the rules below work on the rest
+   let kept: Vec<usize> = (0..cs.len()).filter(|&i| raw[i] != Class::BN).collect();
+   let mut t: Vec<Class> = kept.iter().map(|&i| raw[i]).collect();
+
+   //W1: a nonspacing mark takes the type of the character before it
+   for i in 0..t.len() {
+      if t[i] == Class::NSM { t[i] = if i == 0 { e } else { t[i-1] }; }
+   }
+   //W2 and W3: European numbers after Arabic letters are Arabic numbers, Arabic letters are right-to-left
+   let mut strong = e;
+   for i in 0..t.len() {
+      match t[i] {
+         Class::L | Class::R | Class::AL => { strong = t[i]; }
+         Class::EN if strong == Class::AL => { t[i] = Class::AN; }
+         _ => {}
+      }
+   }
+   for i in 0..t.len() {
+      if t[i] == Class::AL { t[i] = Class::R; }
+   }
+   //W4: a single separator between two numbers of the same type joins them
+   for i in 1..t.len().saturating_sub(1) {
+      match (t[i-1], t[i], t[i+1]) {
+         (Class::EN, Class::ES, Class::EN) | (Class::EN, Class::CS, Class::EN) => { t[i] = Class::EN; }
+         (Class::AN, Class::CS, Class::AN) => { t[i] = Class::AN; }
+         _ => {}
+      }
+   }
+   //W5: terminators next to European numbers are part of them
+   let mut i = 0;
+   while i < t.len() {
+      if t[i] == Class::ET {
+         let mut j = i;
+         while j < t.len() && t[j] == Class::ET { j += 1; }
+         if (i > 0 && t[i-1] == Class::EN) || (j < t.len() && t[j] == Class::EN) {
+            for k in i..j { t[k] = Class::EN; }
+         }
+         i = j;
+      } else { i += 1; }
+   }
+   //W6 and W7: leftover separators are neutral, European numbers after left-to-right text are left-to-right
+   let mut strong = e;
+   for i in 0..t.len() {
+      match t[i] {
+         Class::ES | Class::ET | Class::CS => { t[i] = Class::ON; }
+         Class::L | Class::R => { strong = t[i]; }
+         Class::EN if strong == Class::L => { t[i] = Class::L; }
+         _ => {}
+      }
+   }
+   //N0: a pair of brackets takes the direction of the text inside it, or of the text before it when that disagrees with the embedding
+   let direction = |c: Class| match c {
+      Class::L => { Some(Class::L) }
+      Class::R | Class::EN | Class::AN => { Some(Class::R) }
+      _ => { None }
+   };
+   let mut open: Vec<(usize, char)> = Vec::new();
+   let mut pairs = Vec::new();
+   for k in 0..t.len() {
+      let c = cs[kept[k]];
+      if t[k] != Class::ON { continue; }
+      match c {
+         '(' | '[' | '{' | '\u{FF08}' | '\u{FF3B}' | '\u{FF5B}' => { if open.len() < 63 { open.push((k, mirror(c))); } }
+         ')' | ']' | '}' | '\u{FF09}' | '\u{FF3D}' | '\u{FF5D}' => {
+            if let Some(o) = open.iter().rposition(|&(_, close)| close == c) {
+               pairs.push((open[o].0, k));
+               open.truncate(o);
+            }
+         }
+         _ => {}
+      }
+   }
+   pairs.sort();
+   for (a, b) in pairs {
+      let inside: Vec<Class> = t[a+1..b].iter().filter_map(|&c| direction(c)).collect();
+      let resolved = if inside.contains(&e) {
+         Some(e)
+      } else if let Some(&opposite) = inside.first() {
+         let before = t[..a].iter().rev().filter_map(|&c| direction(c)).next().unwrap_or(e);
+         Some(if before == opposite { opposite } else { e })
+      } else { None };
+      if let Some(resolved) = resolved { t[a] = resolved; t[b] = resolved; }
+   }
+
+   //N1 and N2: neutrals between text of the same direction take it, others take the embedding direction
+   let mut i = 0;
+   while i < t.len() {
+      if direction(t[i]).is_none() {
+         let mut j = i;
+         while j < t.len() && direction(t[j]).is_none() { j += 1; }
+         let before = if i == 0 { e } else { direction(t[i-1]).unwrap_or(e) };
+         let after = if j == t.len() { e } else { direction(t[j]).unwrap_or(e) };
+         let resolved = if before == after { before } else { e };
+         for k in i..j { t[k] = resolved; }
+         i = j;
+      } else { i += 1; }
+   }
+
+   //I1 and I2, then L1 for separators and the whitespace before them
+   let mut out = vec![level; cs.len()];
+   for (k, &i) in kept.iter().enumerate() {
+      out[i] = match (level % 2, t[k]) {
+         (0, Class::R) => { level + 1 }
+         (0, Class::AN) | (0, Class::EN) => { level + 2 }
+         (1, Class::L) | (1, Class::EN) | (1, Class::AN) => { level + 1 }
+         _ => { level }
+      };
+   }
+   let mut reset = true;
+   for i in (0..cs.len()).rev() {
+      match raw[i] {
+         Class::B | Class::S => { out[i] = level; reset = true; }
+         Class::WS | Class::BN if reset => { out[i] = level; }
+         Class::BN => { out[i] = if i > 0 { out[i-1] } else { level }; }
+         _ => { reset = false; }
+      }
+   }
+   out
+}
+
+///The visual order of a line, from left to right, as indices into its characters (L2)
+pub fn reorder(levels: &[u8]) -> Vec<usize> {
+   let mut order: Vec<usize> = (0..levels.len()).collect();
+   let highest = levels.iter().cloned().max().unwrap_or(0);
+   let lowest = levels.iter().cloned().filter(|l| l % 2 == 1).min().unwrap_or(highest + 1);
+   let mut level = highest;
+   while level >= lowest {
+      let mut i = 0;
+      while i < order.len() {
+         if levels[order[i]] >= level {
+            let mut j = i;
+            while j < order.len() && levels[order[j]] >= level { j += 1; }
+            order[i..j].reverse();
+            i = j;
+         } else { i += 1; }
+      }
+      level -= 1;
+   }
+   order
+}
+
+///The mirror image of a character drawn right-to-left, such as ")" for "(" (L4)
+pub fn mirror(c: char) -> char {
+   match c {
+      '(' => { ')' } ')' => { '(' }
+      '<' => { '>' } '>' => { '<' }
+      '[' => { ']' } ']' => { '[' }
+      '{' => { '}' } '}' => { '{' }
+      '\u{AB}' => { '\u{BB}' } '\u{BB}' => { '\u{AB}' }
+      '\u{2039}' => { '\u{203A}' } '\u{203A}' => { '\u{2039}' }
+      '\u{2045}' => { '\u{2046}' } '\u{2046}' => { '\u{2045}' }
+      '\u{2264}' => { '\u{2265}' } '\u{2265}' => { '\u{2264}' }
+      '\u{3008}' => { '\u{3009}' } '\u{3009}' => { '\u{3008}' }
+      '\u{300A}' => { '\u{300B}' } '\u{300B}' => { '\u{300A}' }
+      '\u{300C}' => { '\u{300D}' } '\u{300D}' => { '\u{300C}' }
+      '\u{300E}' => { '\u{300F}' } '\u{300F}' => { '\u{300E}' }
+      '\u{3010}' => { '\u{3011}' } '\u{3011}' => { '\u{3010}' }
+      '\u{FF08}' => { '\u{FF09}' } '\u{FF09}' => { '\u{FF08}' }
+      '\u{FF3B}' => { '\u{FF3D}' } '\u{FF3D}' => { '\u{FF3B}' }
+      '\u{FF5B}' => { '\u{FF5D}' } '\u{FF5D}' => { '\u{FF5B}' }
+      c => { c }
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::{paragraphs, levels, reorder, mirror};
+
+   fn resolve(s: &str, direction: Option<u8>) -> (Vec<u8>, String) {
+      let cs: Vec<char> = s.chars().collect();
+      let ls = levels(&cs, &paragraphs(&cs, direction));
+      let visual = reorder(&ls).into_iter().map(|i| if ls[i] % 2 == 1 { mirror(cs[i]) } else { cs[i] }).collect();
+      (ls, visual)
+   }
+
+   #[test]
+   fn paragraph_levels() {
+      let cs: Vec<char> = "abc\nשלום".chars().collect();
+      assert_eq!(paragraphs(&cs, None), vec![0, 0, 0, 0, 1, 1, 1, 1]);
+      assert_eq!(paragraphs(&cs, Some(1)), vec![1; 8]);
+      //neutrals and numbers before the first strong character don't decide
+      let cs: Vec<char> = "12 مرحبا".chars().collect();
+      assert_eq!(paragraphs(&cs, None), vec![1; 8]);
+      let cs: Vec<char> = "!?".chars().collect();
+      assert_eq!(paragraphs(&cs, None), vec![0, 0]);
+   }
+
+   #[test]
+   fn hebrew() {
+      assert_eq!(resolve("שלום", None), (vec![1; 4], "םולש".to_owned()));
+      assert_eq!(resolve("abc שלום def", None),
+                 (vec![0, 0, 0, 0, 1, 1, 1, 1, 0, 0, 0, 0], "abc םולש def".to_owned()));
+   }
+
+   #[test]
+   fn arabic() {
+      assert_eq!(resolve("مرحبا", None), (vec![1; 5], "ابحرم".to_owned()));
+      //a space between Arabic words stays right-to-left
+      assert_eq!(resolve("مرحبا بالعالم", None).1, "ملاعلاب ابحرم");
+   }
+
+   #[test]
+   fn numbers() {
+      //European digits keep their order at an even level above the Hebrew around them
+      assert_eq!(resolve("עמוד 123", None), (vec![1, 1, 1, 1, 1, 2, 2, 2], "123 דומע".to_owned()));
+      //after Arabic letters they become Arabic numbers, which also read left-to-right
+      assert_eq!(resolve("سعر 50", None), (vec![1, 1, 1, 1, 2, 2], "50 رعس".to_owned()));
+      //a separator between digits joins them into one number
+      assert_eq!(resolve("שקל 1,000.50", None).1, "1,000.50 לקש");
+      //a number in left-to-right text stays put
+      assert_eq!(resolve("abc 123 שלום", None).1, "abc 123 םולש");
+   }
+
+   #[test]
+   fn bracket_pairs() {
+      //N0: left-to-right text in brackets after left-to-right context keeps both brackets with it
+      assert_eq!(resolve("abc (def)", Some(1)), (vec![2; 9], "abc (def)".to_owned()));
+      //brackets around right-to-left text in a right-to-left paragraph are drawn mirrored
+      assert_eq!(resolve("ש(א)", None), (vec![1; 4], "(א)ש".to_owned()));
+      assert_eq!(resolve("abc (שלום)", None).1, "abc (םולש)");
+   }
+
+   #[test]
+   fn mirrored() {
+      assert_eq!(mirror('('), ')');
+      assert_eq!(mirror(']'), '[');
+      assert_eq!(mirror('<'), '>');
+      assert_eq!(mirror('a'), 'a');
+   }
+
+   #[test]
+   fn reordered() {
+      assert_eq!(reorder(&[0, 0, 1, 1, 0]), vec![0, 1, 3, 2, 4]);
+      assert_eq!(reorder(&[1, 2, 2, 1]), vec![3, 1, 2, 0]);
+      assert_eq!(reorder(&[]), Vec::<usize>::new());
+   }
+}
@@ -0,0 +1,84 @@
+use ::backend::{GlyphKey};
+use ::events::{GlyphStats};
+use std::collections::{HashMap, BTreeMap};
+
+///Memory budget for rasterized glyphs unless configured otherwise, in bytes
+pub const GLYPH_BUDGET: usize = 4 * 1024 * 1024;
+
+///Rasterized glyphs, evicting the least recently used once their coverage outgrows a budget
+pub struct GlyphCache<V> {
+   entries: HashMap<GlyphKey, (V, usize, u64)>,
+   order: BTreeMap<u64, GlyphKey>,
+   tick: u64,
+   ///usage counters
+   pub stats: GlyphStats,
+}
+
+impl<V> GlyphCache<V> {
+   ///Creates an empty cache with a budget in bytes
+   pub fn new(budget: usize) -> GlyphCache<V> {
+      let mut stats = GlyphStats::default();
+      stats.budget = budget;
+      GlyphCache {
+         entries: HashMap::new(),
+         order: BTreeMap::new(),
+         tick: 0,
+         stats: stats,
+      }
+   }
+   ///Changes the budget, taking effect at the next trim
+   pub fn set_budget(&mut self, budget: usize) {
+      self.stats.budget = budget;
+   }
+   ///Marks a glyph as used and counts a hit, or counts a miss if it is not cached
+   pub fn lookup(&mut self, key: GlyphKey) -> bool {
+      self.tick += 1;
+      let tick = self.tick;
+      match self.entries.get_mut(&key) {
+         Some(entry) => {
+            self.order.remove(&entry.2);
+            self.order.insert(tick, key);
+            entry.2 = tick;
+            self.stats.hits += 1;
+            true
+         }
+         None => {
+            self.stats.misses += 1;
+            false
+         }
+      }
+   }
+   ///A cached glyph, without marking it as used
+   pub fn get(&self, key: &GlyphKey) -> Option<&V> {
+      self.entries.get(key).map(|e| &e.0)
+   }
+   ///Adds a glyph that takes up some bytes. The cache may exceed its budget until the next trim.
+   pub fn insert(&mut self, key: GlyphKey, value: V, bytes: usize) {
+      self.tick += 1;
+      if let Some(old) = self.entries.insert(key, (value, bytes, self.tick)) {
+         self.order.remove(&old.2);
+         self.stats.bytes -= old.1;
+      }
+      self.order.insert(self.tick, key);
+      self.stats.bytes += bytes;
+      self.stats.glyphs = self.entries.len();
+   }
+   ///Evicts least recently used glyphs until the cache fits its budget, returning their keys
+   pub fn trim(&mut self) -> Vec<GlyphKey> {
+      let mut evicted = Vec::new();
+      while self.stats.bytes > self.stats.budget {
+         let (tick, key) = match self.order.iter().next() {
+            Some((&tick, &key)) => { (tick, key) }
+            None => { break }
+         };
+         self.order.remove(&tick);
+         if let Some(old) = self.entries.remove(&key) {
+            self.stats.bytes -= old.1;
+         }
+         self.stats.evictions += 1;
+         evicted.push(key);
+      }
+      self.stats.glyphs = self.entries.len();
+      evicted
+   }
+}
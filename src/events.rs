@@ -1,4 +1,210 @@
 use std::collections::{HashMap};
+use ::view::{Component, Event};
+use ::render::{Measurer, Fonts};
+use std::rc::{Rc};
+
+extern crate image;
+use self::image::{RgbaImage};
+
+extern crate sdl2;
+use self::sdl2::keyboard::{Keycode};
+
+///Usage of the rasterized glyph cache, cumulative since the window opened
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GlyphStats {
+   ///glyphs found in the cache
+   pub hits: u64,
+
+   ///glyphs that had to be rasterized
+   pub misses: u64,
+
+   ///glyphs dropped to stay within the budget
+   pub evictions: u64,
+
+   ///glyphs currently cached
+   pub glyphs: usize,
+
+   ///coverage bytes currently cached
+   pub bytes: usize,
+
+   ///most coverage bytes kept between frames
+   pub budget: usize,
+}
+
+///The size of laid out text, in pixels, without padding. Round it up to size a box the text fits in.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TextMetrics {
+   ///width of the widest line
+   pub width: f64,
+
+   ///height of all lines
+   pub height: f64,
+
+   ///width of every line, from the top
+   pub lines: Vec<f64>,
+}
+
+///A key of the keyboard, and the modifier keys held along with it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Key {
+   ///SDL key code, such as 13 for Return or 97 for A
+   pub code: i32,
+
+   ///SDL key name, such as "Return", "Left" or "A"
+   pub name: String,
+
+   ///shift was held
+   pub shift: bool,
+
+   ///ctrl was held
+   pub ctrl: bool,
+
+   ///alt was held
+   pub alt: bool,
+
+   ///the command or windows key was held
+   pub gui: bool,
+
+   ///the key was held down long enough for the keyboard to repeat it
+   pub repeat: bool,
+}
+impl Key {
+   ///A key by its SDL name, without modifiers, such as "Return" or "A". Panics for a name SDL doesn't know.
+   pub fn new(name: &str) -> Key {
+      let keycode = Keycode::from_name(name).expect(format!("Invalid key name: {}", name).as_str());
+      Key { code: keycode as i32, name: keycode.name(), shift: false, ctrl: false, alt: false, gui: false, repeat: false }
+   }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// A button of the mouse
+pub enum Button {
+   ///left
+   Left,
+
+   ///middle, often the wheel pressed down
+   Middle,
+
+   ///right
+   Right,
+}
+impl Button {
+   /// Convert a raw string to Button
+   pub fn new(s: &str) -> Button {
+      match s {
+         "left" => { Button::Left }
+         "middle" => { Button::Middle }
+         "right" => { Button::Right }
+         u => { panic!("Invalid Mouse Button: {}", u) }
+      }
+   }
+}
+impl<'a> Into<Button> for &'a str {
+    fn into(self) -> Button {
+       Button::new(self)
+    }
+}
+
+///A mouse event, and where the cursor was when it happened
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mouse {
+   ///the button pressed, released or dragging, if any
+   pub button: Option<Button>,
+
+   ///cursor position in the window, in pixels
+   pub x: f64,
+
+   ///cursor position in the window, in pixels
+   pub y: f64,
+
+   ///how far the wheel turned, right and away from the user being positive, or how far a drag moved since the previous frame
+   pub delta: (f64, f64),
+
+   ///where the button was pressed to start a drag, in pixels
+   pub start: (f64, f64),
+
+   ///presses of the button in quick succession, 2 for a double click
+   pub clicks: usize,
+}
+
+///What happened to call an event listener, and where
+#[derive(Debug, Clone, PartialEq)]
+pub struct Detail {
+   ///the Event being handled
+   pub event: Event,
+
+   ///the box the Component was laid out in, as left, top, right and bottom in window pixels. The whole window for View listeners.
+   pub bbox: (i32, i32, i32, i32),
+
+   ///cursor position in the window, in pixels, if any
+   pub cursor: Option<(f64, f64)>,
+
+   ///cursor position relative to the top left corner of the bbox, before the Component is rotated or scaled
+   pub local: Option<(f64, f64)>,
+
+   ///the mouse event, for mouse Events and clicks
+   pub mouse: Option<Mouse>,
+
+   ///the key, for keyboard Events and Clicked when Enter or Space activates the Component
+   pub key: Option<Key>,
+}
+
+///Keyboard, mouse and text input received during a frame, in the order it happened
+#[derive(Debug, Clone, PartialEq)]
+pub enum Input {
+   ///Input::KeyDown, a key pressed, or repeated while it is held
+   KeyDown(Key),
+
+   ///Input::KeyUp, a key released
+   KeyUp(Key),
+
+   ///Input::MouseDown, a mouse button pressed
+   MouseDown(Button),
+
+   ///Input::MouseUp, a mouse button released
+   MouseUp(Button),
+
+   ///Input::Wheel, the mouse wheel turned, right and away from the user being positive
+   Wheel(f64, f64),
+
+   ///Input::Text, text typed or committed by an input method
+   Text(String),
+
+   ///Input::Composition, text an input method is still composing, with its cursor in chars. Empty when composition ends.
+   Composition(String, usize),
+}
+
+///The TextInput being edited, and its selection
+#[derive(Debug, Clone, PartialEq)]
+pub struct Editing {
+   ///keyval key the TextInput is bound to
+   pub key: String,
+
+   ///where the selection started, in chars
+   pub anchor: usize,
+
+   ///where the caret is, in chars. The selection runs between anchor and caret.
+   pub caret: usize,
+
+   ///text an input method is composing at the caret, and its cursor in chars
+   pub composition: Option<(String, usize)>,
+
+   ///how far a single line TextInput is scrolled to keep the caret in view, in pixels
+   pub scroll: usize,
+
+   ///the caret follows the cursor while the left mouse button stays down
+   pub dragging: bool,
+}
+impl Editing {
+   ///Starts editing a TextInput with the caret at a char index
+   pub fn new(key: &str, caret: usize) -> Editing {
+      Editing { key: key.to_owned(), anchor: caret, caret: caret, composition: None, scroll: 0, dragging: false }
+   }
+   ///The selected chars, from first to last
+   pub fn selection(&self) -> (usize, usize) {
+      if self.anchor < self.caret { (self.anchor, self.caret) } else { (self.caret, self.anchor) }
+   }
+}
 
 ///All user events pass through and/or are recorded in this structure.
 pub struct Events {
@@ -13,6 +219,35 @@ pub struct Events {
 
    /// Time elapsed since program started, measured in seconds
    pub time_elapsed: f64,
+
+   /// Number of frames rendered before this one
+   pub frame: usize,
+
+   /// Pending frame captures, by PNG path. An empty path keeps the capture in memory only.
+   pub captures: Vec<String>,
+
+   /// The most recently captured frame
+   pub captured: Option<RgbaImage>,
+
+   /// Why the most recent captures were not taken or written, if any failed
+   pub capture_error: Option<String>,
+
+   /// Glyph cache usage as of the previous frame
+   pub glyphs: GlyphStats,
+
+   /// The TextInput being edited, if any
+   pub editing: Option<Editing>,
+
+   /// The key of the Component with keyboard focus, if any. Set it to move focus.
+   pub focus: Option<String>,
+
+   /// The keys of the hoverable Components under the cursor as of the previous frame
+   pub hovered: Vec<String>,
+
+   /// Drawable size of the window, in pixels
+   pub window: (usize, usize),
+
+   measurer: Option<Measurer>,
 }
 impl Events {
    ///Creates a new Events object. Used in Window rendering and is not meant for general use.
@@ -22,8 +257,38 @@ impl Events {
          state: "".to_owned(),
          keyval: HashMap::new(),
          time_elapsed: 0.0,
+         frame: 0,
+         captures: Vec::new(),
+         captured: None,
+         capture_error: None,
+         glyphs: GlyphStats::default(),
+         editing: None,
+         focus: None,
+         hovered: Vec::new(),
+         window: (0, 0),
+         measurer: None,
       }
    }
+   ///Creates a new Events object that can measure text with the fonts of a Renderer. Used in Window rendering.
+   pub fn with_fonts(fonts: Rc<Fonts>) -> Events {
+      let mut events = Events::new();
+      events.measurer = Some(Measurer::new(fonts));
+      events
+   }
+   ///Changes the fonts that measured text falls back to. Used in Window rendering.
+   pub fn set_fallback_fonts(&mut self, fonts: &[&str]) {
+      if let Some(ref mut measurer) = self.measurer { measurer.set_fallback_fonts(fonts); }
+   }
+   ///Measure a Text or RichText as it would be laid out if appended to the View this frame.
+   ///None for any other Component, or for Events without fonts, see Events::with_fonts.
+   pub fn measure(&mut self, c: &mut Component) -> Option<TextMetrics> {
+      let window = self.window;
+      self.measurer.as_mut().and_then(|m| m.measure(c, window))
+   }
+   ///Whether the cursor was over the hoverable Component with a key as of the previous frame
+   pub fn is_hovered(&self, key: &str) -> bool {
+      self.hovered.iter().any(|k| k == key)
+   }
    ///Send a method to central dispatch
    pub fn message(&mut self, msg: Vec<String>) {
       self.messages.push( msg )
@@ -36,4 +301,12 @@ impl Events {
    pub fn get(&mut self, key: &str) -> String {
       self.keyval.get(&key.to_string()).unwrap_or(&"".to_string()).clone()
    }
+   ///Capture the current frame once it is rendered and write it to a PNG file
+   pub fn capture(&mut self, path: &str) {
+      self.captures.push( path.to_string() )
+   }
+   ///Capture the current frame once it is rendered and keep it in Events.captured
+   pub fn capture_image(&mut self) {
+      self.captures.push( "".to_string() )
+   }
 }
@@ -0,0 +1,173 @@
+use ::events::{Events, Input, Key, Button};
+use ::view::{View};
+use ::render::{Renderer, Frame, capture};
+use ::backend::{Backend, Software};
+
+extern crate image;
+use self::image::{RgbaImage};
+
+///Renders Views onto a Backend without a display or event loop
+pub struct Headless<B = Software> where B: Backend {
+   renderer: Renderer,
+   backend: B,
+   events: Events,
+   cursor: Option<(usize,usize)>,
+   click: bool,
+   held: bool,
+   input: Vec<Input>,
+   capture_after: Option<(usize,String)>,
+}
+
+impl Headless<Software> {
+   ///Creates a new software renderer of a fixed size. See also Window::headless.
+   pub fn new(assets: &[(String,Vec<u8>)], width: u32, height: u32) -> Headless<Software> {
+      Headless::with_backend(assets, Software::new(width, height))
+   }
+   ///Renders a single frame at a fixed point in time
+   pub fn render<F>(&mut self, time_elapsed: f64, cl: F) -> &RgbaImage
+       where F: FnMut(&mut Events) -> View {
+      self.draw(time_elapsed, cl).frame()
+   }
+   ///The most recently rendered frame
+   pub fn frame(&self) -> &RgbaImage {
+      self.backend.frame()
+   }
+}
+
+impl<B> Headless<B> where B: Backend {
+   ///Creates a new renderer driving any Backend. See also Window::headless_with.
+   pub fn with_backend(assets: &[(String,Vec<u8>)], mut backend: B) -> Headless<B> {
+      let renderer = Renderer::new(assets, &mut backend);
+      let events = Events::with_fonts(renderer.fonts());
+      Headless {
+         renderer: renderer,
+         backend: backend,
+         events: events,
+         cursor: None,
+         click: false,
+         held: false,
+         input: Vec::new(),
+         capture_after: None,
+      }
+   }
+   ///Moves the simulated cursor to a pixel position for the following frames
+   pub fn set_cursor(&mut self, x: usize, y: usize) {
+      self.cursor = Some((x, y));
+   }
+   ///Simulates a left click at the cursor during the next frame
+   pub fn click(&mut self) {
+      self.click = true;
+      self.input.push(Input::MouseDown(Button::Left));
+      self.input.push(Input::MouseUp(Button::Left));
+   }
+   ///Simulates a mouse button pressed at the cursor during the next frame, "left", "middle" or "right". The left button stays held until released.
+   pub fn mouse_down<T>(&mut self, button: T)
+       where T: Into<Button> {
+      let button = button.into();
+      if button == Button::Left {
+         self.click = true;
+         self.held = true;
+      }
+      self.input.push(Input::MouseDown(button));
+   }
+   ///Simulates a mouse button released at the cursor during the next frame, named as in mouse_down
+   pub fn mouse_up<T>(&mut self, button: T)
+       where T: Into<Button> {
+      let button = button.into();
+      if button == Button::Left {
+         self.held = false;
+      }
+      self.input.push(Input::MouseUp(button));
+   }
+   ///Simulates the mouse wheel turning during the next frame, right and away from the user being positive
+   pub fn scroll(&mut self, dx: f64, dy: f64) {
+      self.input.push(Input::Wheel(dx, dy));
+   }
+   ///Holds the left mouse button down, or releases it, for the following frames
+   pub fn hold(&mut self, held: bool) {
+      self.held = held;
+   }
+   ///Simulates a key pressed and released during the next frame, by SDL key name with optional modifiers, e.g. "Left", "Shift+Left" or "Ctrl+A"
+   pub fn press_key(&mut self, key: &str) {
+      self.key_down(key);
+      self.key_up(key);
+   }
+   ///Simulates a key pressed during the next frame, named as in press_key
+   pub fn key_down(&mut self, key: &str) {
+      self.input.push(Input::KeyDown(Headless::<B>::key(key)));
+   }
+   ///Simulates a held key repeated during the next frame, named as in press_key
+   pub fn repeat_key(&mut self, key: &str) {
+      let mut key = Headless::<B>::key(key);
+      key.repeat = true;
+      self.input.push(Input::KeyDown(key));
+   }
+   ///Simulates a key released during the next frame, named as in press_key
+   pub fn key_up(&mut self, key: &str) {
+      self.input.push(Input::KeyUp(Headless::<B>::key(key)));
+   }
+   fn key(key: &str) -> Key {
+      let mut parts: Vec<&str> = key.split('+').collect();
+      let mut key = Key::new(parts.pop().unwrap_or(""));
+      key.shift = parts.contains(&"Shift");
+      key.ctrl = parts.contains(&"Ctrl");
+      key.alt = parts.contains(&"Alt");
+      key.gui = parts.contains(&"Gui");
+      key
+   }
+   ///Simulates typing text during the next frame
+   pub fn type_text(&mut self, text: &str) {
+      self.input.push(Input::Text(text.to_owned()));
+   }
+   ///Simulates an input method composing text during the next frame, with its cursor in chars
+   pub fn compose(&mut self, text: &str, cursor: usize) {
+      self.input.push(Input::Composition(text.to_owned(), cursor));
+   }
+   ///Writes a frame to a PNG file once the given number of frames have been rendered
+   pub fn set_capture_after(&mut self, frames: usize, path: &str) {
+      self.capture_after = Some((frames, path.to_owned()));
+   }
+   ///Changes how many bytes of rasterized glyphs are kept between frames
+   pub fn set_glyph_budget(&mut self, bytes: usize) {
+      self.renderer.set_glyph_budget(bytes);
+   }
+   ///Changes the font assets that every Text draws missing characters from, after its own fallbacks
+   pub fn set_fallback_fonts(&mut self, fonts: &[&str]) {
+      self.renderer.set_fallback_fonts(fonts);
+      self.events.set_fallback_fonts(fonts);
+   }
+   ///Events shared by all frames of this renderer
+   pub fn events(&mut self) -> &mut Events {
+      &mut self.events
+   }
+   ///The Backend being drawn to
+   pub fn backend(&mut self) -> &mut B {
+      &mut self.backend
+   }
+   ///Draws and presents a single frame at a fixed point in time
+   pub fn draw<F>(&mut self, time_elapsed: f64, mut cl: F) -> &mut B
+       where F: FnMut(&mut Events) -> View {
+      self.events.time_elapsed = time_elapsed;
+      let (width, height) = self.backend.size();
+      let frame = Frame {
+         width: width as usize,
+         height: height as usize,
+         cursor: self.cursor,
+         click: self.click,
+         held: self.held,
+         input: self.input.drain(..).collect(),
+      };
+      self.click = false;
+      match self.capture_after {
+         Some((n, ref path)) if n == self.events.frame => { self.events.capture(path.as_str()); }
+         _ => {}
+      }
+      self.events.window = (frame.width, frame.height);
+      let mut v = cl(&mut self.events);
+      self.renderer.render(&mut self.backend, &mut v, &mut self.events, &frame);
+      capture(&mut self.backend, &mut self.events);
+      self.backend.present();
+      self.events.frame += 1;
+      &mut self.backend
+   }
+}
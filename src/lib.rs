@@ -11,6 +11,27 @@ pub mod events;
 /// Implements the render cycle and exposes a very simple API for creating and manipulating windows
 pub mod window;
 
+/// Render targets that the render cycle draws to
+pub mod backend;
+
+/// Offscreen rendering of Views, for tests and previews
+pub mod headless;
+
+/// Golden-image snapshot testing of Views
+pub mod snapshot;
+
+/// Layout and drawing shared by every render target
+mod render;
+
+/// Line break opportunities in text
+mod linebreak;
+
+/// Visual order of bidirectional text
+mod bidi;
+
+/// Least recently used cache of rasterized glyphs
+mod cache;
+
 /// Tools to be used from build.rs scripts
 pub mod build;
 
@@ -0,0 +1,148 @@
+///Whether a line may end before a character
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Break {
+   ///the line must continue
+   Prohibited,
+
+   ///the line may end here
+   Allowed,
+
+   ///the line must end here
+   Mandatory,
+}
+
+///The line breaking classes of Unicode Standard Annex #14 that the pair rules below tell apart
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Class {
+   BK, CR, LF, SP, ZW, CM, GL, BA, HY, OP, CL, EX, IS, ID, AL,
+}
+
+fn class(c: char) -> Class {
+   match c {
+      '\n' => { Class::LF }
+      '\r' => { Class::CR }
+      '\u{0B}' | '\u{0C}' | '\u{85}' | '\u{2028}' | '\u{2029}' => { Class::BK }
+      ' ' => { Class::SP }
+      '\u{200B}' => { Class::ZW }
+      '\u{200D}' | '\u{0300}'..='\u{036F}' | '\u{1AB0}'..='\u{1AFF}' | '\u{1DC0}'..='\u{1DFF}' |
+      '\u{20D0}'..='\u{20FF}' | '\u{FE00}'..='\u{FE0F}' | '\u{FE20}'..='\u{FE2F}' => { Class::CM }
+      '\u{A0}' | '\u{2007}' | '\u{202F}' | '\u{2060}' | '\u{FEFF}' => { Class::GL }
+      '\t' | '\u{AD}' | '|' | '/' | '\u{2000}'..='\u{2006}' | '\u{2008}'..='\u{200A}' |
+      '\u{2010}' | '\u{2012}' | '\u{2013}' | '\u{3000}' => { Class::BA }
+      '-' => { Class::HY }
+      '(' | '[' | '{' | '\u{A1}' | '\u{BF}' | '\u{3008}' | '\u{300A}' | '\u{300C}' | '\u{300E}' | '\u{3010}' |
+      '\u{FF08}' | '\u{FF3B}' | '\u{FF5B}' => { Class::OP }
+      ')' | ']' | '}' | '\u{3001}' | '\u{3002}' | '\u{3009}' | '\u{300B}' | '\u{300D}' | '\u{300F}' | '\u{3011}' |
+      '\u{FF09}' | '\u{FF0C}' | '\u{FF0E}' | '\u{FF3D}' | '\u{FF5D}' => { Class::CL }
+      '!' | '?' | '\u{FF01}' | '\u{FF1F}' => { Class::EX }
+      ',' | '.' | ':' | ';' => { Class::IS }
+      '\u{2E80}'..='\u{2FFF}' | '\u{3040}'..='\u{30FF}' | '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}' |
+      '\u{AC00}'..='\u{D7AF}' | '\u{F900}'..='\u{FAFF}' | '\u{FF00}'..='\u{FF60}' |
+      '\u{1F300}'..='\u{1FAFF}' | '\u{20000}'..='\u{3FFFD}' => { Class::ID }
+      _ => { Class::AL }
+   }
+}
+
+///Find the line break opportunities of a string, following the core pair rules of Unicode Standard Annex #14.
+///breaks[i] tells whether a line may end before cs[i]; a line may always end after the last character.
+pub fn breaks(cs: &[char]) -> Vec<Break> {
+   let raw: Vec<Class> = cs.iter().map(|&c| class(c)).collect();
+
+   //LB9 and LB10: combining marks take the class of their base, or AL when there is none
+   let mut resolved = raw.clone();
+   for i in 0..resolved.len() {
+      if raw[i] == Class::CM {
+         resolved[i] = match i.checked_sub(1).map(|p| resolved[p]) {
+            Some(Class::BK) | Some(Class::CR) | Some(Class::LF) | Some(Class::SP) | Some(Class::ZW) | None => { Class::AL }
+            Some(base) => { base }
+         };
+      }
+   }
+
+   let mut out = vec![Break::Prohibited; cs.len()];
+   //the class of the last character that is not a space, for the rules that look across SP*
+   let mut before_spaces = Class::AL;
+   for i in 1..cs.len() {
+      let (a, b) = (resolved[i-1], raw[i]);
+      if a != Class::SP { before_spaces = a; }
+      out[i] = match (a, b) {
+         (Class::CR, Class::LF) => { Break::Prohibited }
+         (Class::BK, _) | (Class::CR, _) | (Class::LF, _) => { Break::Mandatory }
+         (_, Class::BK) | (_, Class::CR) | (_, Class::LF) | (_, Class::SP) | (_, Class::ZW) => { Break::Prohibited }
+         _ if before_spaces == Class::ZW => { Break::Allowed }
+         (_, Class::CM) => { Break::Prohibited }
+         (Class::GL, _) | (_, Class::GL) => { Break::Prohibited }
+         (_, Class::CL) | (_, Class::EX) | (_, Class::IS) => { Break::Prohibited }
+         _ if before_spaces == Class::OP => { Break::Prohibited }
+         (Class::SP, _) => { Break::Allowed }
+         (_, Class::BA) | (_, Class::HY) => { Break::Prohibited }
+         (Class::ID, _) | (_, Class::ID) => { Break::Allowed }
+         (Class::AL, Class::AL) | (Class::IS, Class::AL) | (Class::AL, Class::OP) | (Class::CL, Class::AL) => { Break::Prohibited }
+         _ => { Break::Allowed }
+      };
+   }
+   out
+}
+
+#[cfg(test)]
+mod tests {
+   use super::{breaks, Break};
+   use super::Break::{Prohibited, Allowed, Mandatory};
+
+   fn of(s: &str) -> Vec<Break> {
+      breaks(&s.chars().collect::<Vec<char>>())
+   }
+
+   #[test]
+   fn mandatory() {
+      assert_eq!(of("a\r\nb"), vec![Prohibited, Prohibited, Prohibited, Mandatory]);
+      assert_eq!(of("a\rb"), vec![Prohibited, Prohibited, Mandatory]);
+      assert_eq!(of("a\nb"), vec![Prohibited, Prohibited, Mandatory]);
+      assert_eq!(of("a\u{2028}b"), vec![Prohibited, Prohibited, Mandatory]);
+   }
+
+   #[test]
+   fn spaces() {
+      assert_eq!(of("a b"), vec![Prohibited, Prohibited, Allowed]);
+      assert_eq!(of("a  b"), vec![Prohibited, Prohibited, Prohibited, Allowed]);
+      assert_eq!(of("ab"), vec![Prohibited, Prohibited]);
+   }
+
+   #[test]
+   fn zero_width_space() {
+      assert_eq!(of("a\u{200B}b"), vec![Prohibited, Prohibited, Allowed]);
+      assert_eq!(of("a\u{200B} b"), vec![Prohibited, Prohibited, Prohibited, Allowed]);
+   }
+
+   #[test]
+   fn glue() {
+      assert_eq!(of("a\u{A0}b"), vec![Prohibited, Prohibited, Prohibited]);
+      assert_eq!(of("10\u{202F}%"), vec![Prohibited, Prohibited, Prohibited, Prohibited]);
+   }
+
+   #[test]
+   fn brackets() {
+      //nothing breaks after an opening bracket, even across spaces, or before a closing one
+      assert_eq!(of("(a"), vec![Prohibited, Prohibited]);
+      assert_eq!(of("( a"), vec![Prohibited, Prohibited, Prohibited]);
+      assert_eq!(of("a )"), vec![Prohibited, Prohibited, Prohibited]);
+      assert_eq!(of("a (b) c"), vec![Prohibited, Prohibited, Allowed, Prohibited, Prohibited, Prohibited, Allowed]);
+      assert_eq!(of("a!"), vec![Prohibited, Prohibited]);
+   }
+
+   #[test]
+   fn hyphens_and_marks() {
+      assert_eq!(of("a-b"), vec![Prohibited, Prohibited, Allowed]);
+      assert_eq!(of("a\u{AD}b"), vec![Prohibited, Prohibited, Allowed]);
+      //a combining mark stays with its base
+      assert_eq!(of("e\u{301} b"), vec![Prohibited, Prohibited, Prohibited, Allowed]);
+   }
+
+   #[test]
+   fn cjk() {
+      assert_eq!(of("中文字"), vec![Prohibited, Allowed, Allowed]);
+      assert_eq!(of("中文。"), vec![Prohibited, Allowed, Prohibited]);
+      assert_eq!(of("中（文）"), vec![Prohibited, Allowed, Prohibited, Prohibited]);
+      assert_eq!(of("ab中"), vec![Prohibited, Prohibited, Allowed]);
+   }
+}
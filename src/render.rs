@@ -0,0 +1,2156 @@
+use ::events::{Events, Input, Editing, TextMetrics, Key, Button, Mouse, Detail};
+use ::view::{View, Component, Modifier, ViewUnit, Length, AlignUnit, AngleUnit, Group, TextInput, Layout, JustifyUnit, CrossUnit, Track, TrackUnit, WrapUnit, OverflowUnit, VerticalAlignUnit, DirectionUnit};
+
+extern crate rusttype;
+use self::rusttype::{FontCollection, Font, Scale, point};
+
+extern crate unicode_normalization;
+
+extern crate image;
+
+use ::backend::{Backend, Glyph, GlyphKey, Rect, Rotation};
+use ::linebreak::{self, Break};
+use ::bidi;
+use ::cache::{GlyphCache, GLYPH_BUDGET};
+use ::view::{Event};
+use std::collections::{HashMap};
+use std::cell::{RefCell};
+use std::rc::{Rc};
+use std::f64::consts::{PI};
+
+const EM: f64 = 22.0;
+
+///Input state and drawable size of a single frame
+pub struct Frame {
+   ///drawable width in pixels
+   pub width: usize,
+
+   ///drawable height in pixels
+   pub height: usize,
+
+   ///cursor position in pixels, if any
+   pub cursor: Option<(usize,usize)>,
+
+   ///left mouse button was pressed this frame
+   pub click: bool,
+
+   ///left mouse button is held down
+   pub held: bool,
+
+   ///keyboard, mouse and text input received since the previous frame
+   pub input: Vec<Input>,
+}
+
+///U+00AD, drawn as a hyphen only where a line breaks after it
+const SOFT_HYPHEN: char = '\u{AD}';
+
+///U+2026, drawn where Text is truncated
+const ELLIPSIS: char = '\u{2026}';
+
+///Most seconds between the presses of a double click
+const DOUBLE_CLICK_TIME: f64 = 0.5;
+
+///Most pixels the cursor may move between the presses of a double click
+const DOUBLE_CLICK_DISTANCE: f64 = 4.0;
+
+///Pixels the cursor moves with the left button held before a press becomes a drag
+const DRAG_DISTANCE: f64 = 4.0;
+
+///A run of text sharing one font, size, color and decoration
+struct Run {
+   font: String,
+
+   ///NFC normalized, except in a TextInput where every char is a caret position
+   content: String,
+   pixel_height: usize,
+   color: [f64; 4],
+   underline: bool,
+   strikethrough: bool,
+   line_height: Option<usize>,
+   letter_spacing: f32,
+   word_spacing: f32,
+   direction: DirectionUnit,
+}
+impl Run {
+   ///A run with the same style, drawing other content in another font
+   fn part(&self, font: &str, content: String) -> Run {
+      Run { font: font.to_owned(), content: content, pixel_height: self.pixel_height, color: self.color,
+            underline: self.underline, strikethrough: self.strikethrough,
+            line_height: self.line_height, letter_spacing: self.letter_spacing, word_spacing: self.word_spacing,
+            direction: self.direction }
+   }
+}
+
+///One line of broken text
+#[derive(Clone)]
+struct Line {
+   ///(pen position, char, run, advance, bidi level) in logical order
+   glyphs: Vec<(f32, char, usize, f32, u8)>,
+   caret: f32,
+
+   ///the width of the line without trailing whitespace
+   width: f32,
+
+   ///the height of the tallest run on the line
+   height: usize,
+
+   ///the distance from the top of the line to its baseline
+   ascent: f32,
+
+   ///the last line of a paragraph
+   last: bool,
+
+   ///the char that kerns with the next one, and its run
+   prev: Option<(char, usize)>,
+
+   ///the bidi level of the paragraph, odd if it runs right-to-left
+   level: u8,
+}
+impl Line {
+   fn new(level: u8) -> Line {
+      Line { glyphs: Vec::new(), caret: 0.0, width: 0.0, height: 0, ascent: 0.0, last: false, prev: None, level: level }
+   }
+   fn push(&mut self, c: char, run: usize, (kerning, advance): (f32, f32), (height, ascent): (usize, f32), level: u8) {
+      self.caret += kerning;
+      self.glyphs.push((self.caret, c, run, advance, level));
+      self.caret += advance;
+      if !c.is_whitespace() && advance > 0.0 { self.width = self.caret; }
+      if c != SOFT_HYPHEN && !c.is_control() { self.prev = Some((c, run)); }
+      if height > self.height { self.height = height; }
+      if ascent > self.ascent { self.ascent = ascent; }
+   }
+   ///Finish a line, drawing a soft hyphen that it ends in. Empty lines take the metrics of the run they are in.
+   fn end(mut self, last: bool, hyphens: &[f32], (height, ascent): (usize, f32)) -> Line {
+      if !last {
+         if let Some(&(x, SOFT_HYPHEN, run, _, level)) = self.glyphs.last() {
+            self.glyphs.pop();
+            self.glyphs.push((x, '-', run, hyphens[run], level));
+            self.width = x + hyphens[run];
+         }
+      }
+      if self.height == 0 { self.height = height; self.ascent = ascent; }
+      self.last = last; self
+   }
+   ///Whether any of the line runs right-to-left
+   fn bidi(&self) -> bool {
+      self.level % 2 == 1 || self.glyphs.iter().any(|g| g.4 % 2 == 1)
+   }
+   ///The glyphs of the line without its trailing whitespace
+   fn visible(&self) -> usize {
+      self.glyphs.iter().rposition(|g| !(g.1.is_whitespace() || g.1.is_control())).map(|i| i + 1).unwrap_or(0)
+   }
+   ///The glyphs of the line from left to right, with their index and placed where they are drawn.
+   ///Trailing whitespace runs on from the end of the paragraph, off the left of a right-to-left line.
+   fn visual(&self) -> Vec<(usize, (f32, char, usize, f32, u8))> {
+      if !self.bidi() { return self.glyphs.iter().cloned().enumerate().collect() }
+      let visible = self.visible();
+      let levels: Vec<u8> = self.glyphs[..visible].iter().map(|g| g.4).collect();
+      //the kerning before a glyph stays with it
+      let kerning = |i: usize| self.glyphs[i].0 - if i == 0 { 0.0 } else { self.glyphs[i-1].0 + self.glyphs[i-1].3 };
+      let mut x = 0.0;
+      let mut out = Vec::with_capacity(self.glyphs.len());
+      for i in bidi::reorder(&levels) {
+         let (_, c, run, advance, level) = self.glyphs[i];
+         out.push((i, (x + kerning(i), c, run, advance, level)));
+         x += kerning(i) + advance;
+      }
+      let mut trailing = Vec::new();
+      let mut left = 0.0;
+      for i in visible..self.glyphs.len() {
+         let (_, c, run, advance, _) = self.glyphs[i];
+         if self.level % 2 == 1 {
+            left -= advance;
+            trailing.push((i, (left, c, run, advance, self.level)));
+         } else {
+            trailing.push((i, (x, c, run, advance, self.level)));
+            x += advance;
+         }
+      }
+      if self.level % 2 == 1 {
+         trailing.reverse();
+         trailing.extend(out);
+         trailing
+      } else {
+         out.extend(trailing);
+         out
+      }
+   }
+   ///Where the caret before each glyph is drawn, and the caret after the last one.
+   ///A caret sits on the leading edge of a glyph: its left if it runs left-to-right, its right otherwise.
+   fn carets(&self) -> Vec<f32> {
+      if !self.bidi() {
+         return self.glyphs.iter().map(|g| g.0).chain(Some(self.caret)).collect()
+      }
+      let mut carets = vec![0.0; self.glyphs.len() + 1];
+      for (i, g) in self.visual() {
+         carets[i] = if g.4 % 2 == 1 { g.0 + g.3 } else { g.0 };
+         if i + 1 == self.glyphs.len() {
+            carets[i + 1] = if g.4 % 2 == 1 { g.0 } else { g.0 + g.3 };
+         }
+      }
+      carets
+   }
+}
+
+///A glyph bitmap placed in a paragraph
+struct Placed {
+   x: i32,
+   y: i32,
+   key: GlyphKey,
+   width: usize,
+   height: usize,
+   run: usize,
+}
+
+///A rasterized glyph and the metrics to place it in a run of text
+struct Raster {
+   glyph: Glyph,
+
+   ///offset of the bitmap from the pen position
+   left: i32,
+
+   ///distance the pen moves after this glyph
+   advance: f32,
+}
+
+///A rigid motion from local coordinates to window coordinates: a rotation followed by a translation
+#[derive(Clone, Copy)]
+struct Motion {
+   radians: f64,
+   x: f64,
+   y: f64,
+}
+impl Motion {
+   fn identity() -> Motion {
+      Motion { radians: 0.0, x: 0.0, y: 0.0 }
+   }
+   fn translate(x: f64, y: f64) -> Motion {
+      Motion { radians: 0.0, x: x, y: y }
+   }
+   ///A clockwise rotation about a pivot point
+   fn rotate(radians: f64, x: f64, y: f64) -> Motion {
+      let (sin, cos) = radians.sin_cos();
+      Motion { radians: radians, x: x - (x*cos - y*sin), y: y - (x*sin + y*cos) }
+   }
+   fn apply(&self, x: f64, y: f64) -> (f64, f64) {
+      let (sin, cos) = self.radians.sin_cos();
+      (x*cos - y*sin + self.x, x*sin + y*cos + self.y)
+   }
+   fn invert(&self, x: f64, y: f64) -> (f64, f64) {
+      let (sin, cos) = self.radians.sin_cos();
+      let (dx, dy) = (x - self.x, y - self.y);
+      (dx*cos + dy*sin, dy*cos - dx*sin)
+   }
+   ///Apply inner first, then self
+   fn compose(&self, inner: &Motion) -> Motion {
+      let (x, y) = self.apply(inner.x, inner.y);
+      Motion { radians: self.radians + inner.radians, x: x, y: y }
+   }
+   ///Express this motion of a rectangle as a Backend Rect and Rotation
+   fn place(&self, r: Rect) -> (Rect, Rotation) {
+      let (sin, cos) = self.radians.sin_cos();
+      if sin.abs() < 1e-9 && cos > 0.0 {
+         let r = Rect::new(r.x + self.x.round() as i32, r.y + self.y.round() as i32, r.w, r.h);
+         (r, Rotation::new(0.0, r.x as f64, r.y as f64))
+      } else {
+         //any other rigid motion is a rotation about the fixed point p = Rp + t
+         let det = (1.0 - cos)*(1.0 - cos) + sin*sin;
+         let px = ((1.0 - cos)*self.x - sin*self.y) / det;
+         let py = (sin*self.x + (1.0 - cos)*self.y) / det;
+         (r, Rotation::new(self.radians, px, py))
+      }
+   }
+}
+
+///The box that a Component is laid out in
+struct Space {
+   width: usize,
+   height: usize,
+   motion: Motion,
+}
+impl Space {
+   ///Resolve a scalar and ViewUnit to pixels. "%" is measured against axis_pct, one percent of the chosen axis.
+   fn length(&self, scalar: f64, unit: &ViewUnit, axis_pct: f64) -> f64 {
+      let width_pct = (self.width as f64) / 100.0;
+      let height_pct = (self.height as f64) / 100.0;
+      match *unit {
+        ViewUnit::Em => { EM * scalar }
+        ViewUnit::Percent => { axis_pct * scalar }
+        ViewUnit::HorizontalPercent => { width_pct * scalar }
+        ViewUnit::VerticalPercent => { height_pct * scalar }
+        ViewUnit::MaxPercent => { let pct = if height_pct>width_pct { height_pct } else { width_pct }; pct * scalar }
+        ViewUnit::MinPercent => { let pct = if height_pct<width_pct { height_pct } else { width_pct }; pct * scalar }
+        ViewUnit::Pixel => { scalar.trunc() }
+        ViewUnit::Center => { panic!("Center is not a length") }
+        ViewUnit::Calc(ref l) => { self.calc(l, axis_pct) * scalar }
+      }
+   }
+   ///Evaluate a Length expression to pixels
+   fn calc(&self, l: &Length, axis_pct: f64) -> f64 {
+      match *l {
+        Length::Unit(scalar, ref unit) => { self.length(scalar, unit, axis_pct) }
+        Length::Add(ref a, ref b) => { self.calc(a, axis_pct) + self.calc(b, axis_pct) }
+        Length::Sub(ref a, ref b) => { self.calc(a, axis_pct) - self.calc(b, axis_pct) }
+        Length::Min(ref a, ref b) => { self.calc(a, axis_pct).min(self.calc(b, axis_pct)) }
+        Length::Max(ref a, ref b) => { self.calc(a, axis_pct).max(self.calc(b, axis_pct)) }
+        Length::Clamp(ref lo, ref v, ref hi) => { self.calc(v, axis_pct).min(self.calc(hi, axis_pct)).max(self.calc(lo, axis_pct)) }
+      }
+   }
+   fn horizontal_pct(&self) -> f64 {
+      (self.width as f64) / 100.0
+   }
+   fn vertical_pct(&self) -> f64 {
+      (self.height as f64) / 100.0
+   }
+}
+
+///Where a container's layout placed a child, in the container's coordinates
+#[derive(Clone, Copy)]
+struct Slot {
+   x: i32,
+   y: i32,
+   width: usize,
+   height: usize,
+
+   ///the box that "=" translations center in, starting at x and y
+   area: (usize, usize),
+}
+
+///The Modifiers of a Component resolved to pixels inside its parent box
+struct Resolved {
+   pixel_height: usize,
+   width: Option<usize>,
+   height: Option<usize>,
+   pos_x: i32,
+   pos_y: i32,
+   center_x: bool,
+   center_y: bool,
+   cog: (f64,f64),
+   color: [f64; 4],
+   shadow: ([i64; 4],[f64; 4]),
+   border_width: usize,
+   border_color: [f64; 4],
+   radians: f64,
+   gap: usize,
+   grow: f64,
+   shrink: f64,
+
+   ///top, right, bottom, left
+   padding: [i32; 4],
+
+   ///top, right, bottom, left
+   margin: [i32; 4],
+   min_width: Option<usize>,
+   max_width: Option<usize>,
+   min_height: Option<usize>,
+   max_height: Option<usize>,
+   max_lines: Option<usize>,
+   overflow: OverflowUnit,
+   line_height: Option<usize>,
+   letter_spacing: f64,
+   word_spacing: f64,
+   vertical_align: VerticalAlignUnit,
+   direction: DirectionUnit,
+   tab_index: i32,
+   focus_ring: Option<([f64; 4], usize)>,
+}
+impl Resolved {
+   ///Apply the Min and Max constraints of one axis to a size. Min wins over Max.
+   fn clamp(&self, size: usize, horizontal: bool) -> usize {
+      let (min, max) = if horizontal { (self.min_width, self.max_width) } else { (self.min_height, self.max_height) };
+      let size = match max { Some(max) if size > max => { max } _ => { size } };
+      match min { Some(min) if size < min => { min } _ => { size } }
+   }
+   ///The margins before and after the box on one axis
+   fn margins(&self, horizontal: bool) -> (i32, i32) {
+      if horizontal { (self.margin[3], self.margin[1]) } else { (self.margin[0], self.margin[2]) }
+   }
+   ///The padding before and after the content on one axis
+   fn paddings(&self, horizontal: bool) -> (i32, i32) {
+      if horizontal { (self.padding[3], self.padding[1]) } else { (self.padding[0], self.padding[2]) }
+   }
+}
+
+///The fonts decoded from some assets by name, each with the index of its asset
+pub type Fonts = HashMap<String, (usize, Font<'static>)>;
+
+///Lays out Components, draws them to a Backend and dispatches their events
+pub struct Renderer {
+   fonts: Rc<Fonts>,
+   glyphs: GlyphCache<Raster>,
+   fallbacks: Vec<String>,
+   focus: Focus,
+   pointer: Pointer,
+}
+
+///Where keyboard focus can go, and where it has been
+#[derive(Default)]
+struct Focus {
+   ///the keys of the focusable Components of this frame in View order, with their tab index
+   order: Vec<(i32, String)>,
+
+   ///the focusable Component clicked this frame
+   clicked: Option<String>,
+
+   ///Events.focus as of the previous frame
+   last: Option<String>,
+
+   ///the keys that lost and took focus since the previous frame
+   changed: (Option<String>, Option<String>),
+}
+
+///Where the mouse has been, to tell double clicks and drags
+#[derive(Default)]
+struct Pointer {
+   ///the button, time and position of the latest press, and how many presses came in quick succession
+   last_press: Option<(Button, f64, (f64, f64), usize)>,
+
+   ///how many presses came in quick succession, for every press of this frame
+   presses: Vec<usize>,
+
+   ///Components visited so far this frame, numbering them in View order
+   visited: usize,
+
+   ///the last Component in View order with drag listeners under a left press this frame
+   grabbed: Option<Target>,
+
+   ///the Component the held left button was pressed on, and where
+   pressed: Option<(Target, (f64, f64))>,
+
+   ///the pressed Component moved far enough to be dragged
+   dragging: bool,
+
+   ///what happens to the drag this frame
+   drag: Option<Event>,
+
+   ///how far the cursor moved since the previous frame
+   moved: (f64, f64),
+
+   ///cursor position as of the previous frame
+   last_cursor: Option<(f64, f64)>,
+
+   ///the Components under the cursor so far this frame
+   under: Vec<Target>,
+
+   ///the Components under the cursor as of the previous frame
+   last_under: Vec<Target>,
+}
+
+///A Component told apart across frames, by its hover key or else its place in the View
+#[derive(Clone, PartialEq)]
+enum Target {
+   Key(String),
+   Place(usize),
+}
+impl Pointer {
+   ///Count the presses of a frame and tell how its drag goes on
+   fn begin(&mut self, time_elapsed: f64, frame: &Frame) {
+      let cursor = frame.cursor.map(|(x, y)| (x as f64, y as f64));
+      let at = cursor.unwrap_or((0.0, 0.0));
+      self.visited = 0;
+      self.grabbed = None;
+      self.under.clear();
+      self.presses.clear();
+      for i in frame.input.iter() {
+         if let Input::MouseDown(button) = *i {
+            let clicks = match self.last_press {
+               Some((b, t, (x, y), clicks)) if b == button && time_elapsed - t <= DOUBLE_CLICK_TIME &&
+                                               (at.0 - x).abs() <= DOUBLE_CLICK_DISTANCE &&
+                                               (at.1 - y).abs() <= DOUBLE_CLICK_DISTANCE => { clicks + 1 }
+               _ => { 1 }
+            };
+            self.last_press = Some((button, time_elapsed, at, clicks));
+            self.presses.push(clicks);
+         }
+      }
+      self.moved = match (self.last_cursor, cursor) {
+         (Some(last), Some(at)) => { (at.0 - last.0, at.1 - last.1) }
+         _ => { (0.0, 0.0) }
+      };
+      self.drag = match self.pressed {
+         Some(_) if !frame.held => { if self.dragging { Some(Event::DragEnd) } else { None } }
+         Some((_, start)) if !self.dragging => {
+            if (at.0 - start.0).hypot(at.1 - start.1) >= DRAG_DISTANCE {
+               self.dragging = true;
+               Some(Event::DragStart)
+            } else { None }
+         }
+         Some(_) if self.moved != (0.0, 0.0) => { Some(Event::DragMove) }
+         _ => { None }
+      };
+   }
+   ///Remember where a held left press landed, once every Component has seen the frame
+   fn end(&mut self, frame: &Frame) {
+      let cursor = frame.cursor.map(|(x, y)| (x as f64, y as f64));
+      if !frame.held {
+         self.pressed = None;
+         self.dragging = false;
+      }
+      if frame.click && frame.held {
+         self.pressed = self.grabbed.take().map(|g| (g, cursor.unwrap_or((0.0, 0.0))));
+         self.dragging = false;
+      }
+      self.last_cursor = cursor;
+      self.last_under = ::std::mem::replace(&mut self.under, Vec::new());
+   }
+}
+
+impl Renderer {
+   ///Decode all assets, handing images over to the Backend
+   pub fn new<B>(assets: &[(String,Vec<u8>)], backend: &mut B) -> Renderer
+       where B: Backend {
+      let mut fonts = HashMap::new();
+      for (index, &(ref name, ref buf)) in assets.iter().enumerate() {
+         if name.ends_with(".png") {
+            let png = image::load_from_memory_with_format(buf, image::ImageFormat::PNG).expect("Couldn't load image");
+            backend.load_image(name.as_str(), &png.to_rgba());
+         }
+         else if name.ends_with(".ttf") {
+            let font = FontCollection::from_bytes(buf.clone()).into_font().expect("single ttf font file");
+            fonts.insert(name.clone(), (index, font));
+         } else {
+            panic!("Unrecognized asset file format: {}", name)
+         }
+      }
+      Renderer {
+         fonts: Rc::new(fonts),
+         glyphs: GlyphCache::new(GLYPH_BUDGET),
+         fallbacks: Vec::new(),
+         focus: Focus::default(),
+         pointer: Pointer::default(),
+      }
+   }
+
+   ///The decoded fonts, to share with Events::with_fonts
+   pub fn fonts(&self) -> Rc<Fonts> {
+      self.fonts.clone()
+   }
+
+   ///Changes how many bytes of rasterized glyphs are kept between frames
+   pub fn set_glyph_budget(&mut self, bytes: usize) {
+      self.glyphs.set_budget(bytes);
+   }
+
+   ///Render one View and fire the events of its Components
+   pub fn render<B>(&mut self, backend: &mut B, v: &mut View, events: &mut Events, frame: &Frame)
+       where B: Backend {
+      backend.clear([0.0, 0.0, 0.0, 1.0]);
+
+      //a TextInput set to be edited takes focus, unless focus was just taken away
+      if events.focus.is_none() && self.focus.last.is_none() {
+         events.focus = events.editing.as_ref().map(|e| e.key.clone());
+      }
+      //focus changes made since the previous frame are announced during this one
+      self.focus.changed = (None, None);
+      if self.focus.last != events.focus {
+         self.focus.changed = (self.focus.last.take(), events.focus.clone());
+         self.focus.last = events.focus.clone();
+      }
+      self.focus.order.clear();
+      self.focus.clicked = None;
+      Renderer::follow_focus(events);
+      self.pointer.begin(events.time_elapsed, frame);
+
+      let cursor = frame.cursor.map(|(x, y)| (x as f64, y as f64));
+      let detail = Detail { event: Event::Always, bbox: (0, 0, frame.width as i32, frame.height as i32),
+                            cursor: cursor, local: cursor, mouse: None, key: None };
+      Renderer::key_events(&v.events, events, frame, &detail);
+
+      let window = Space { width: frame.width, height: frame.height, motion: Motion::identity() };
+      for c in v.components.iter_mut() {
+         self.component(backend, c, events, frame, &window, None);
+      }
+
+      //a click focuses what it lands on, or nothing, then Tab moves on from there
+      if frame.click {
+         events.focus = self.focus.clicked.take();
+      }
+      for i in frame.input.iter() {
+         match *i {
+            Input::KeyDown(ref k) if k.name == "Tab" => { self.tab(events, k.shift); }
+            _ => {}
+         }
+      }
+      Renderer::follow_focus(events);
+      self.pointer.end(frame);
+      events.hovered = self.pointer.last_under.iter().filter_map(|t| match *t {
+         Target::Key(ref k) => { Some(k.clone()) }
+         Target::Place(_) => { None }
+      }).collect();
+
+      for key in self.glyphs.trim() {
+         backend.evict_glyph(key);
+      }
+      events.glyphs = self.glyphs.stats;
+   }
+
+   ///Call the key listeners among some listeners for every key of this frame in turn, with that key in their Detail
+   fn key_events(listeners: &[(Event, Rc<RefCell<FnMut(&mut Events, &Detail)>>)], events: &mut Events, frame: &Frame, detail: &Detail) {
+      for i in frame.input.iter() {
+         let (event, key) = match *i {
+            Input::KeyDown(ref k) if k.repeat => { (Event::KeyRepeat, k) }
+            Input::KeyDown(ref k) => { (Event::KeyDown, k) }
+            Input::KeyUp(ref k) => { (Event::KeyUp, k) }
+            _ => { continue }
+         };
+         for &(ref ev, ref f) in listeners.iter() {
+            if *ev != event { continue }
+            let detail = Detail { event: event.clone(), key: Some(key.clone()), .. detail.clone() };
+            let mut callback = f.borrow_mut();
+            (&mut *callback)(events, &detail);
+         }
+      }
+   }
+
+   ///Call the mouse listeners among some listeners of the Component told apart as target, with each mouse event in turn in their Detail
+   fn mouse_events(&self, listeners: &[(Event, Rc<RefCell<FnMut(&mut Events, &Detail)>>)], events: &mut Events, frame: &Frame, target: &Target, hovered: bool, edge: Option<Event>, detail: &Detail) {
+      let (x, y) = frame.cursor.map(|(x, y)| (x as f64, y as f64)).unwrap_or((0.0, 0.0));
+      let mouse = Mouse { button: None, x: x, y: y, delta: (0.0, 0.0), start: (x, y), clicks: 0 };
+      let mut fired = Vec::new();
+      if let Some(edge) = edge {
+         fired.push((edge, mouse.clone()));
+      }
+      if hovered {
+         let mut presses = self.pointer.presses.iter();
+         for i in frame.input.iter() {
+            match *i {
+               Input::MouseDown(button) => {
+                  let clicks = presses.next().cloned().unwrap_or(1);
+                  let m = Mouse { button: Some(button), clicks: clicks, .. mouse.clone() };
+                  fired.push((Event::MouseDown, m.clone()));
+                  if clicks == 2 { fired.push((Event::DoubleClick, m)); }
+               }
+               Input::MouseUp(button) => { fired.push((Event::MouseUp, Mouse { button: Some(button), .. mouse.clone() })); }
+               Input::Wheel(dx, dy) => { fired.push((Event::Wheel, Mouse { delta: (dx, dy), .. mouse.clone() })); }
+               _ => {}
+            }
+         }
+      }
+      match (&self.pointer.pressed, &self.pointer.drag) {
+         (&Some((ref pressed, start)), &Some(ref drag)) if pressed == target => {
+            fired.push((drag.clone(), Mouse { button: Some(Button::Left), delta: self.pointer.moved, start: start, .. mouse.clone() }));
+         }
+         _ => {}
+      }
+      for (event, m) in fired {
+         for &(ref ev, ref f) in listeners.iter() {
+            if *ev != event { continue }
+            let detail = Detail { event: event.clone(), mouse: Some(m.clone()), .. detail.clone() };
+            let mut callback = f.borrow_mut();
+            (&mut *callback)(events, &detail);
+         }
+      }
+   }
+
+   ///The key a Component is hovered by, if it has one
+   fn hover_key(c: &mut Component) -> Option<String> {
+      for m in c.modifiers() {
+         match *m {
+            Modifier::Hoverable(ref s) => { return Some(s.key.clone()) }
+            _ => {}
+         }
+      }
+      Renderer::focus_key(c)
+   }
+
+   ///The key a Component takes focus by, if it can
+   fn focus_key(c: &mut Component) -> Option<String> {
+      if let Component::TextInput(ref input) = *c {
+         return Some(input.key.clone())
+      }
+      for m in c.modifiers() {
+         match *m {
+            Modifier::Focusable(ref s) => { return Some(s.key.clone()) }
+            _ => {}
+         }
+      }
+      None
+   }
+
+   ///Move focus to the next focusable Component in Tab order, or the previous one
+   fn tab(&mut self, events: &mut Events, back: bool) {
+      let mut order: Vec<&(i32, String)> = self.focus.order.iter().filter(|f| f.0 >= 0).collect();
+      order.sort_by_key(|f| if f.0 > 0 { (0, f.0) } else { (1, 0) });
+      if order.is_empty() { return }
+      let n = order.len();
+      let next = match (order.iter().position(|f| Some(&f.1) == events.focus.as_ref()), back) {
+         (Some(p), false) => { (p + 1) % n }
+         (Some(p), true) => { (p + n - 1) % n }
+         (None, false) => { 0 }
+         (None, true) => { n - 1 }
+      };
+      events.focus = Some(order[next].1.clone());
+   }
+
+   ///Stop editing a TextInput that no longer has focus
+   fn follow_focus(events: &mut Events) {
+      if events.editing.as_ref().map(|e| Some(&e.key) != events.focus.as_ref()).unwrap_or(false) {
+         events.editing = None;
+      }
+   }
+
+   ///Whether the Conditionals of a Component allow it to be rendered
+   fn visible(c: &mut Component, events: &mut Events) -> bool {
+      for m in c.modifiers() {
+         match *m {
+            Modifier::Conditional(ref cnd) => {
+               if events.get(cnd.key.as_str()) != cnd.val {
+                  return false
+               }
+            }
+            _ => { }
+         }
+      }
+      true
+   }
+
+   ///Resolve the Modifiers of a Component inside its parent box
+   fn resolve(c: &mut Component, parent: &Space) -> Resolved {
+      let width_pct = parent.horizontal_pct();
+      let height_pct = parent.vertical_pct();
+      let mut r = Resolved {
+         pixel_height: EM as usize,
+         width: None,
+         height: None,
+         pos_x: 0,
+         pos_y: 0,
+         center_x: false,
+         center_y: false,
+         cog: (0.0, 0.0),
+         color: [1.0, 1.0, 1.0, 1.0],
+         shadow: ([0,0,0,0],[0.0,0.0,0.0,0.0]),
+         border_width: 0,
+         border_color: [0.0, 0.0, 0.0, 0.0],
+         radians: 0.0,
+         gap: 0,
+         grow: 0.0,
+         shrink: 1.0,
+         padding: [0, 0, 0, 0],
+         margin: [0, 0, 0, 0],
+         min_width: None,
+         max_width: None,
+         min_height: None,
+         max_height: None,
+         max_lines: None,
+         overflow: OverflowUnit::Visible,
+         line_height: None,
+         letter_spacing: 0.0,
+         word_spacing: 0.0,
+         vertical_align: VerticalAlignUnit::Top,
+         direction: DirectionUnit::Auto,
+         tab_index: 0,
+         focus_ring: None,
+      };
+
+      for m in c.modifiers() {
+         match *m {
+            Modifier::Conditional(_) => {}
+            Modifier::State(_) => {}
+            Modifier::Shadow(ref s) => {
+               r.shadow = (s.boxed, s.rgba);
+            }
+            Modifier::Color(ref s) => {
+               r.color = s.rgba;
+            }
+            Modifier::Border(ref s) => {
+               r.border_width = match s.unit {
+                 ViewUnit::Percent | ViewUnit::Center => { panic!("Invalid unit for border: {:?}", s.unit) }
+                 ref u => { parent.length(s.scalar, u, width_pct).ceil() as usize }
+               };
+               r.border_color = s.rgba;
+            }
+            Modifier::Scale(ref s) => {
+               r.pixel_height = match s.unit {
+                 ViewUnit::Center => { panic!("Invalid unit for scale: {:?}", s.unit) }
+                 ref u => { parent.length(s.scalar, u, height_pct).ceil() as usize }
+               };
+            }
+            Modifier::Width(ref s) => {
+               r.width = match s.unit {
+                 ViewUnit::Center => { panic!("Invalid unit for width: {:?}", s.unit) }
+                 ref u => { Some(parent.length(s.scalar, u, width_pct).ceil() as usize) }
+               };
+            }
+            Modifier::Height(ref s) => {
+               r.height = match s.unit {
+                 ViewUnit::Center => { panic!("Invalid unit for height: {:?}", s.unit) }
+                 ref u => { Some(parent.length(s.scalar, u, height_pct).ceil() as usize) }
+               };
+            }
+            Modifier::Angle(ref s) => {
+               match s.unit {
+                 AngleUnit::Degree => { r.radians = (s.scalar / 360.0) * (2.0 * PI); }
+                 AngleUnit::Radian => { r.radians = s.scalar; }
+                 AngleUnit::Hour => { r.radians = (s.scalar / 12.0) * (2.0 * PI); }
+               }
+            }
+            Modifier::CenterOfGravity(ref s) => {
+               r.cog = (s.horizontal, s.vertical);
+            }
+            Modifier::TranslateX(ref s) => {
+               match s.unit {
+                 ViewUnit::Center => { r.center_x = true; }
+                 ref u => { r.pos_x += parent.length(s.scalar, u, width_pct).ceil() as i32; }
+               }
+            }
+            Modifier::TranslateY(ref s) => {
+               match s.unit {
+                 ViewUnit::Center => { r.center_y = true; }
+                 ref u => { r.pos_y += parent.length(s.scalar, u, height_pct).ceil() as i32; }
+               }
+            }
+            Modifier::Gap(ref s) => {
+               r.gap = match s.unit {
+                 ViewUnit::Center => { panic!("Invalid unit for gap: {:?}", s.unit) }
+                 ref u => { parent.length(s.scalar, u, width_pct).ceil() as usize }
+               };
+            }
+            Modifier::Grow(ref s) => {
+               r.grow = s.scalar;
+            }
+            Modifier::Shrink(ref s) => {
+               r.shrink = s.scalar;
+            }
+            Modifier::Cell(_) => {}
+            Modifier::Span(_) => {}
+            Modifier::Padding(ref s) => {
+               for side in 0..4 {
+                  let axis_pct = if side % 2 == 0 { height_pct } else { width_pct };
+                  r.padding[side] += match s.unit {
+                    ViewUnit::Center => { panic!("Invalid unit for padding: {:?}", s.unit) }
+                    ref u => { parent.length(s.sides[side], u, axis_pct).ceil() as i32 }
+                  };
+               }
+            }
+            Modifier::Margin(ref s) => {
+               for side in 0..4 {
+                  let axis_pct = if side % 2 == 0 { height_pct } else { width_pct };
+                  r.margin[side] += match s.unit {
+                    ViewUnit::Center => { panic!("Invalid unit for margin: {:?}", s.unit) }
+                    ref u => { parent.length(s.sides[side], u, axis_pct).ceil() as i32 }
+                  };
+               }
+            }
+            Modifier::MinWidth(ref s) => {
+               r.min_width = match s.unit {
+                 ViewUnit::Center => { panic!("Invalid unit for min width: {:?}", s.unit) }
+                 ref u => { Some(parent.length(s.scalar, u, width_pct).ceil() as usize) }
+               };
+            }
+            Modifier::MaxWidth(ref s) => {
+               r.max_width = match s.unit {
+                 ViewUnit::Center => { panic!("Invalid unit for max width: {:?}", s.unit) }
+                 ref u => { Some(parent.length(s.scalar, u, width_pct).ceil() as usize) }
+               };
+            }
+            Modifier::MinHeight(ref s) => {
+               r.min_height = match s.unit {
+                 ViewUnit::Center => { panic!("Invalid unit for min height: {:?}", s.unit) }
+                 ref u => { Some(parent.length(s.scalar, u, height_pct).ceil() as usize) }
+               };
+            }
+            Modifier::MaxHeight(ref s) => {
+               r.max_height = match s.unit {
+                 ViewUnit::Center => { panic!("Invalid unit for max height: {:?}", s.unit) }
+                 ref u => { Some(parent.length(s.scalar, u, height_pct).ceil() as usize) }
+               };
+            }
+            Modifier::MaxLines(ref s) => {
+               r.max_lines = Some(s.lines);
+            }
+            Modifier::Overflow(ref s) => {
+               r.overflow = s.unit;
+            }
+            Modifier::LineHeight(ref s) => {
+               r.line_height = match s.unit {
+                 ViewUnit::Center => { panic!("Invalid unit for line height: {:?}", s.unit) }
+                 ref u => { Some(parent.length(s.scalar, u, height_pct).round() as usize) }
+               };
+            }
+            Modifier::LetterSpacing(ref s) => {
+               r.letter_spacing = match s.unit {
+                 ViewUnit::Center => { panic!("Invalid unit for letter spacing: {:?}", s.unit) }
+                 ref u => { parent.length(s.scalar, u, width_pct) }
+               };
+            }
+            Modifier::WordSpacing(ref s) => {
+               r.word_spacing = match s.unit {
+                 ViewUnit::Center => { panic!("Invalid unit for word spacing: {:?}", s.unit) }
+                 ref u => { parent.length(s.scalar, u, width_pct) }
+               };
+            }
+            Modifier::VerticalAlign(ref s) => {
+               r.vertical_align = s.unit;
+            }
+            Modifier::Direction(ref s) => {
+               r.direction = s.unit;
+            }
+            Modifier::Focusable(_) | Modifier::Hoverable(_) => {}
+            Modifier::TabIndex(ref s) => {
+               r.tab_index = s.index;
+            }
+            Modifier::FocusRing(ref s) => {
+               let width = match s.unit {
+                 ViewUnit::Percent | ViewUnit::Center => { panic!("Invalid unit for focus ring: {:?}", s.unit) }
+                 ref u => { parent.length(s.scalar, u, width_pct).ceil() as usize }
+               };
+               r.focus_ring = Some((s.rgba, width));
+            }
+         }
+      }
+      r
+   }
+
+   ///The cache key of a character in a font
+   fn key(&self, font: &str, c: char, pixel_height: usize) -> GlyphKey {
+      let &(index, ref font) = self.fonts.get(font).expect(format!("Could not find font: {}", font).as_str());
+      GlyphKey { font: index, glyph: font.glyph(c).expect("glyph").id().0, size: pixel_height }
+   }
+
+   ///Rasterize every glyph of a string that is not cached yet
+   fn load_glyphs(&mut self, font: &str, content: &str, pixel_height: usize) {
+      for c in content.chars() {
+         let key = self.key(font, c, pixel_height);
+         if !self.glyphs.lookup(key) {
+            let &(_, ref font) = self.fonts.get(font).expect(format!("Could not find font: {}", font).as_str());
+            let scale = Scale { x: (pixel_height) as f32, y: (pixel_height as f32) };
+            let offset = point(0.0, font.v_metrics(scale).ascent);
+
+            let g = font.glyph(c).expect("glyph").scaled(scale);
+            let advance = g.h_metrics().advance_width;
+            let g = g.positioned(offset);
+            let (left, width) = match g.pixel_bounding_box() {
+               Some(bb) => { (bb.min.x, (bb.max.x - bb.min.x) as usize) }
+               None => { (0, 0) }
+            };
+            let mut rasterized_glyph = vec![0u8; width * pixel_height];
+            if let Some(bb) = g.pixel_bounding_box() {
+               g.draw(|x, y, v| {
+                  let w = (v * 255.0) as u8;
+                  let y = y as i32 + bb.min.y;
+                  if (x as usize) < width && y >= 0 && y < pixel_height as i32 {
+                     rasterized_glyph[x as usize + (y as usize) * width] = w;
+                  }
+               })
+            }
+            let bytes = rasterized_glyph.len();
+            let glyph = Glyph { width: width, height: pixel_height, coverage: rasterized_glyph };
+            self.glyphs.insert(key, Raster { glyph: glyph, left: left, advance: advance }, bytes);
+         };
+      }
+   }
+
+   ///The size of a paragraph laid out without wrapping, up to a number of lines, and the baseline of its first line
+   fn natural_size(&mut self, runs: &[Run], wrap: WrapUnit, max_lines: Option<usize>) -> (usize, usize, f32) {
+      let mut lines = self.break_lines(runs, wrap, None);
+      if let Some(max) = max_lines { lines.truncate(max); }
+      let width = lines.iter().map(|l| l.width).fold(0.0, f32::max);
+      (width.ceil() as usize, lines.iter().map(|l| l.height).sum(), lines.first().map(|l| l.ascent).unwrap_or(0.0))
+   }
+
+   ///The kerning before a character that follows prev, and the distance the pen moves after it
+   fn advance(&self, font: &str, prev: Option<char>, c: char, pixel_height: usize) -> (f32, f32) {
+      if c == SOFT_HYPHEN || c.is_control() { return (0.0, 0.0) }
+      //a glyph missing from the cache is measured from its font, which gives the .notdef glyph if it has none
+      let advance = match (self.glyphs.get(&self.key(font, c, pixel_height)), self.fonts.get(font)) {
+         (Some(g), _) => { g.advance }
+         (None, Some(&(_, ref f))) => {
+            f.glyph(c).map(|g| g.scaled(Scale::uniform(pixel_height as f32)).h_metrics().advance_width).unwrap_or(0.0)
+         }
+         (None, None) => { 0.0 }
+      };
+      let kerning = match (prev, self.fonts.get(font)) {
+         (Some(prev), Some(&(_, ref font))) => { font.pair_kerning(Scale::uniform(pixel_height as f32), prev, c) }
+         _ => { 0.0 }
+      };
+      (kerning, advance)
+   }
+
+   ///The kerning and advance of a character in a run, kerning only against the same run. The advance includes letter and word spacing.
+   fn run_advance(&self, runs: &[Run], prev: Option<(char, usize)>, c: char, run: usize) -> (f32, f32) {
+      let prev = match prev { Some((p, pr)) if pr == run => { Some(p) } _ => { None } };
+      let (kerning, advance) = self.advance(&runs[run].font, prev, c, runs[run].pixel_height);
+      if c == SOFT_HYPHEN || c.is_control() { return (kerning, advance) }
+      let spacing = runs[run].letter_spacing + if c.is_whitespace() { runs[run].word_spacing } else { 0.0 };
+      (kerning, advance + spacing)
+   }
+
+   ///The distance from the top of a line of a font to its baseline
+   fn ascent(&self, font: &str, pixel_height: usize) -> f32 {
+      let &(_, ref font) = self.fonts.get(font).expect(format!("Could not find font: {}", font).as_str());
+      font.v_metrics(Scale::uniform(pixel_height as f32)).ascent
+   }
+
+   ///Break a paragraph into lines no wider than width, if any, at the line break opportunities a WrapUnit allows
+   fn break_lines(&mut self, runs: &[Run], wrap: WrapUnit, width: Option<usize>) -> Vec<Line> {
+      let mut cs = Vec::new();
+      let mut hyphens = Vec::new();
+      let mut metrics = Vec::new();
+      for (ri, run) in runs.iter().enumerate() {
+         self.load_glyphs(&run.font, run.content.as_str(), run.pixel_height);
+         self.load_glyphs(&run.font, "-", run.pixel_height);
+         hyphens.push(self.advance(&run.font, None, '-', run.pixel_height).1);
+         //a line height spreads its leading evenly above and below the glyphs
+         let ascent = self.ascent(&run.font, run.pixel_height);
+         metrics.push(match run.line_height {
+            Some(line_height) => { (line_height, ascent + ((line_height as f32) - (run.pixel_height as f32)) / 2.0) }
+            None => { (run.pixel_height, ascent) }
+         });
+         for c in run.content.chars() { cs.push((c, ri)); }
+      }
+      let chars: Vec<char> = cs.iter().map(|c| c.0).collect();
+
+      //bidi levels come from whole paragraphs, and right-to-left glyphs draw mirrored brackets
+      let direction = match runs.first().map(|r| r.direction) {
+         Some(DirectionUnit::LeftToRight) => { Some(0) }
+         Some(DirectionUnit::RightToLeft) => { Some(1) }
+         _ => { None }
+      };
+      let paragraphs = bidi::paragraphs(&chars, direction);
+      let levels = bidi::levels(&chars, &paragraphs);
+      for i in 0..cs.len() {
+         let mirrored = if levels[i] % 2 == 1 { bidi::mirror(cs[i].0) } else { cs[i].0 };
+         if mirrored != cs[i].0 {
+            let run = &runs[cs[i].1];
+            self.load_glyphs(&run.font, mirrored.to_string().as_str(), run.pixel_height);
+            cs[i].0 = mirrored;
+         }
+      }
+      let paragraph = |i: usize| paragraphs.get(i).or(paragraphs.last()).cloned().unwrap_or(direction.unwrap_or(0));
+
+      let breaks = match wrap {
+         WrapUnit::Character => { linebreak::breaks(&chars).into_iter().map(|b| if b == Break::Prohibited { Break::Allowed } else { b }).collect() }
+         _ => { linebreak::breaks(&chars) }
+      };
+      let mut current = metrics.get(0).cloned().unwrap_or((0, 0.0));
+
+      let mut lines = Vec::new();
+      let mut line = Line::new(paragraph(0));
+      let mut i = 0;
+      while i < cs.len() {
+         //a segment runs up to the next break opportunity, and trailing whitespace doesn't need to fit
+         let mut j = i + 1;
+         while j < cs.len() && breaks[j] == Break::Prohibited { j += 1; }
+         let mut visible = j;
+         while visible > i && (chars[visible-1].is_whitespace() || chars[visible-1].is_control()) { visible -= 1; }
+         let mut needed = 0.0;
+         let mut prev = line.prev;
+         for &(c, ri) in cs[i..visible].iter() {
+            let (kerning, advance) = self.run_advance(runs, prev, c, ri);
+            needed += kerning + advance;
+            if advance > 0.0 { prev = Some((c, ri)); }
+         }
+         if visible > i && chars[visible-1] == SOFT_HYPHEN { needed += hyphens[cs[visible-1].1]; }
+
+         if let Some(width) = width {
+            let width = width as f32;
+            if line.caret > 0.0 && line.caret + needed > width {
+               lines.push(line.end(false, &hyphens, current));
+               line = Line::new(paragraph(i));
+            }
+            if line.caret == 0.0 && needed > width && wrap == WrapUnit::BreakWord {
+               for (k, &(c, ri)) in cs[i..j].iter().enumerate() {
+                  let advance = self.run_advance(runs, line.prev, c, ri);
+                  if line.caret > 0.0 && line.caret + advance.0 + advance.1 > width && !c.is_whitespace() {
+                     lines.push(line.end(false, &hyphens, current));
+                     line = Line::new(paragraph(i + k));
+                  }
+                  let advance = self.run_advance(runs, line.prev, c, ri);
+                  current = metrics[ri];
+                  line.push(c, ri, advance, current, levels[i + k]);
+               }
+               i = j;
+               if i < cs.len() && breaks[i] == Break::Mandatory {
+                  lines.push(line.end(true, &hyphens, current));
+                  line = Line::new(paragraph(i));
+               }
+               continue;
+            }
+         }
+         for (k, &(c, ri)) in cs[i..j].iter().enumerate() {
+            let advance = self.run_advance(runs, line.prev, c, ri);
+            current = metrics[ri];
+            line.push(c, ri, advance, current, levels[i + k]);
+         }
+         i = j;
+         if i < cs.len() && breaks[i] == Break::Mandatory {
+            lines.push(line.end(true, &hyphens, current));
+            line = Line::new(paragraph(i));
+         }
+      }
+      lines.push(line.end(true, &hyphens, current));
+      lines
+   }
+
+   ///The characters of an ellipsis in the font of a run, and their width
+   fn ellipsis(&mut self, runs: &[Run], run: usize) -> (Vec<char>, f32) {
+      let (font, pixel_height) = (runs[run].font.as_str(), runs[run].pixel_height);
+      let dots = if self.has_glyph(font, ELLIPSIS) { vec![ELLIPSIS] } else { vec!['.'; 3] };
+      let dots_str: String = dots.iter().collect();
+      self.load_glyphs(font, dots_str.as_str(), pixel_height);
+      let width = dots.iter().map(|&c| self.advance(font, None, c, pixel_height).1).sum();
+      (dots, width)
+   }
+
+   ///The run of the last visible glyph of a line
+   fn last_run(line: &Line) -> usize {
+      line.glyphs.iter().rev().find(|g| !g.1.is_whitespace() && !g.1.is_control()).map(|g| g.2).unwrap_or(0)
+   }
+
+   ///Drop glyphs from the end of a line until an ellipsis fits after them within width
+   fn ellipsize_end(&mut self, runs: &[Run], mut line: Line, width: usize) -> Line {
+      let run = Renderer::last_run(&line);
+      let (dots, dots_width) = self.ellipsis(runs, run);
+      let mut end;
+      loop {
+         while line.glyphs.last().map(|g| g.1.is_whitespace() || g.1.is_control() || g.1 == SOFT_HYPHEN).unwrap_or(false) {
+            line.glyphs.pop();
+         }
+         end = line.glyphs.last().map(|g| g.0 + g.3).unwrap_or(0.0);
+         if end + dots_width <= width as f32 || line.glyphs.is_empty() { break; }
+         line.glyphs.pop();
+      }
+      for c in dots {
+         let advance = self.advance(&runs[run].font, None, c, runs[run].pixel_height).1;
+         line.glyphs.push((end, c, run, advance, line.level));
+         end += advance;
+      }
+      line.caret = end;
+      line.width = end;
+      line.last = true;
+      line
+   }
+
+   ///Join the start of one line to the end of another with an ellipsis, within width
+   fn ellipsize_middle(&mut self, runs: &[Run], head: &Line, tail: &Line, width: usize) -> Line {
+      let run = Renderer::last_run(head);
+      let (dots, dots_width) = self.ellipsis(runs, run);
+      let available = (width as f32 - dots_width).max(0.0);
+
+      //the end gets up to half of the room, the start whatever the end leaves
+      let visible = tail.glyphs.iter().take_while(|g| g.0 < tail.width).count();
+      let mut from = visible;
+      while from > 0 && tail.width - tail.glyphs[from-1].0 <= available / 2.0 { from -= 1; }
+      while from < visible && tail.glyphs[from].1.is_whitespace() { from += 1; }
+      let tail_width = if from < visible { tail.width - tail.glyphs[from].0 } else { 0.0 };
+
+      let mut line = Line::new(head.level);
+      line.glyphs = head.glyphs.iter().cloned().take_while(|g| g.0 + g.3 <= available - tail_width).collect();
+      while line.glyphs.last().map(|g| g.1.is_whitespace() || g.1.is_control() || g.1 == SOFT_HYPHEN).unwrap_or(false) {
+         line.glyphs.pop();
+      }
+      let mut end = line.glyphs.last().map(|g| g.0 + g.3).unwrap_or(0.0);
+      for c in dots {
+         let advance = self.advance(&runs[run].font, None, c, runs[run].pixel_height).1;
+         line.glyphs.push((end, c, run, advance, line.level));
+         end += advance;
+      }
+      if from < visible {
+         let start = tail.glyphs[from].0;
+         for &(caret, c, ri, advance, level) in tail.glyphs[from..visible].iter() {
+            line.glyphs.push((end + caret - start, c, ri, advance, level));
+         }
+         end += tail_width;
+      }
+      line.caret = end;
+      line.width = end;
+      line.height = head.height.max(tail.height);
+      line.ascent = head.ascent.max(tail.ascent);
+      line.last = true;
+      line
+   }
+
+   ///Drop the lines past MaxLines, and past the height unless overflow is visible, then mark the truncation with an ellipsis if asked
+   fn truncate(&mut self, runs: &[Run], mut lines: Vec<Line>, (width, height): (usize, Option<usize>), r: &Resolved) -> Vec<Line> {
+      let mut keep = lines.len();
+      if let Some(max) = r.max_lines { keep = keep.min(max); }
+      if let (Some(height), true) = (height, r.overflow != OverflowUnit::Visible) {
+         let mut bottom = 0;
+         keep = keep.min(lines.iter().take_while(|l| { bottom += l.height; bottom <= height }).count());
+      }
+      let dropped = lines.split_off(keep);
+      let truncated = !dropped.is_empty();
+      let last = lines.len();
+      let wide = width as f32;
+      match r.overflow {
+         OverflowUnit::Ellipsis => {
+            lines.into_iter().enumerate().map(|(li, line)| {
+               if (truncated && li + 1 == last) || line.width > wide { self.ellipsize_end(runs, line, width) } else { line }
+            }).collect()
+         }
+         OverflowUnit::MiddleEllipsis => {
+            //the end of the text is its last line that has something to show
+            let end = dropped.iter().rev().find(|l| l.width > 0.0).cloned();
+            lines.into_iter().enumerate().map(|(li, line)| {
+               match end {
+                  Some(ref tail) if li + 1 == last => { self.ellipsize_middle(runs, &line, tail, width) }
+                  _ if line.width > wide => { self.ellipsize_middle(runs, &line, &line, width) }
+                  _ => { line }
+               }
+            }).collect()
+         }
+         _ => { lines }
+      }
+   }
+
+   ///Place the glyph bitmaps of a paragraph inside a box, along with underline and strikethrough rectangles.
+   ///Lines that do not fit the height are truncated as the Overflow of the Text asks if the height clips.
+   fn layout_text(&mut self, runs: &[Run], align: &AlignUnit, wrap: WrapUnit, (width, height): (usize, usize), clip: bool, r: &Resolved)
+      -> (Vec<Placed>, Vec<(Rect, usize)>) {
+      let lines = self.break_lines(runs, wrap, Some(width));
+      let lines = self.truncate(runs, lines, (width, if clip { Some(height) } else { None }), r);
+      self.place(runs, &lines, align, (width, height), r)
+   }
+
+   ///Where the first line of a paragraph starts in a box of some height, as the VerticalAlign of the Text asks
+   fn text_top(lines: &[Line], height: usize, r: &Resolved) -> i32 {
+      let extra = (height as i32) - lines.iter().map(|l| l.height as i32).sum::<i32>();
+      match r.vertical_align {
+         VerticalAlignUnit::Top | VerticalAlignUnit::Baseline => { 0 }
+         VerticalAlignUnit::Middle => { extra / 2 }
+         VerticalAlignUnit::Bottom => { extra }
+      }
+   }
+
+   ///Place the glyph bitmaps of broken lines inside a box, along with underline and strikethrough rectangles
+   fn place(&mut self, runs: &[Run], lines: &[Line], align: &AlignUnit, (width, height): (usize, usize), r: &Resolved)
+      -> (Vec<Placed>, Vec<(Rect, usize)>) {
+      let mut positioned = Vec::new();
+      let mut decorations = Vec::new();
+      let mut y = Renderer::text_top(lines, height, r);
+      for line in lines.iter() {
+         let extra = ((width as f32) - line.width).max(0.0);
+         //glyphs are drawn from left to right, so a right-to-left line starts at the end of its text
+         let glyphs: Vec<(f32, char, usize, f32, u8)> = line.visual().into_iter().map(|g| g.1).collect();
+         let rtl = line.level % 2 == 1;
+         //word gaps are runs of whitespace between the visible glyphs of a line
+         let gaps = glyphs.iter().enumerate()
+                    .filter(|&(gi, g)| g.1.is_whitespace() && gi + 1 < glyphs.len() && !glyphs[gi+1].1.is_whitespace() && g.0 >= 0.0 && g.0 < line.width)
+                    .count();
+         let mut gap = 0;
+         let mut decoration: Option<(usize, f32, f32)> = None;
+         for (gi, &(caret, c, ri, advance, _)) in glyphs.iter().enumerate() {
+            let offset = match *align {
+               AlignUnit::Left => { 0.0 }
+               AlignUnit::Center => { (extra / 2.0).ceil() }
+               AlignUnit::Right => { extra }
+               AlignUnit::Start => { if rtl { extra } else { 0.0 } }
+               AlignUnit::End => { if rtl { 0.0 } else { extra } }
+               AlignUnit::Justify => {
+                  if line.last || gaps == 0 { if rtl { extra } else { 0.0 } } else { (extra * (gap as f32) / (gaps as f32)).floor() }
+               }
+            };
+            if c.is_whitespace() && gi + 1 < glyphs.len() && !glyphs[gi+1].1.is_whitespace() && caret >= 0.0 { gap += 1; }
+            if c.is_control() || c == SOFT_HYPHEN { continue; }
+            let run = &runs[ri];
+
+            //decorations cover a run up to the end of its last visible glyph on the line
+            if run.underline || run.strikethrough {
+               let caret = caret.max(0.0);
+               let end = (caret + advance).min(line.width).max(caret) + offset;
+               decoration = match decoration {
+                  Some((dr, start, before)) if dr == ri => { Some((dr, start, end.max(before))) }
+                  other => {
+                     if let Some(d) = other { decorations.extend(self.decorate(runs, d, y, line.ascent)); }
+                     Some((ri, caret + offset, end))
+                  }
+               };
+            } else if let Some(d) = decoration.take() {
+               decorations.extend(self.decorate(runs, d, y, line.ascent));
+            }
+
+            let key = self.key(&run.font, c, run.pixel_height);
+            let raster = self.glyphs.get(&key).expect("glyph");
+            if raster.glyph.width == 0 { continue; }
+            let baseline = (line.ascent - self.ascent(&run.font, run.pixel_height)).round() as i32;
+            positioned.push(Placed { x: (caret + offset).round() as i32 + raster.left, y: y + baseline, key: key,
+                                     width: raster.glyph.width, height: run.pixel_height, run: ri });
+         }
+         if let Some(d) = decoration.take() {
+            decorations.extend(self.decorate(runs, d, y, line.ascent));
+         }
+         y += line.height as i32;
+      }
+      (positioned, decorations)
+   }
+
+   ///The underline and strikethrough of a run from start to end on a line
+   fn decorate(&self, runs: &[Run], (ri, start, end): (usize, f32, f32), y: i32, ascent: f32) -> Vec<(Rect, usize)> {
+      let run = &runs[ri];
+      let thickness = ((run.pixel_height as f32) / 16.0).ceil().max(1.0) as u32;
+      let x = start.round() as i32;
+      let w = (end.round() as i32 - x).max(0) as u32;
+      let baseline = y as f32 + ascent;
+      let mut rects = Vec::new();
+      if run.underline {
+         rects.push((Rect::new(x, (baseline + (thickness as f32)).round() as i32, w, thickness), ri));
+      }
+      if run.strikethrough {
+         let middle = baseline - self.ascent(&run.font, run.pixel_height) * 0.3;
+         rects.push((Rect::new(x, middle.round() as i32, w, thickness), ri));
+      }
+      rects
+   }
+
+   ///Whether a font has a glyph for a character, rather than drawing its missing glyph box
+   fn has_glyph(&self, font: &str, c: char) -> bool {
+      let &(_, ref font) = self.fonts.get(font).expect(format!("Could not find font: {}", font).as_str());
+      font.glyph(c).map(|g| g.id().0 != 0).unwrap_or(false)
+   }
+
+   ///Changes the fonts that every Text falls back to, after its own fallbacks
+   pub fn set_fallback_fonts(&mut self, fonts: &[&str]) {
+      for font in fonts {
+         if !self.fonts.contains_key(*font) { panic!("Could not find font: {}", font) }
+      }
+      self.fallbacks = fonts.iter().map(|f| f.to_string()).collect();
+   }
+
+   ///Split a run wherever its font lacks a glyph, drawing those characters from the first fallback font that has them
+   fn fall_back(&self, run: Run, fallbacks: &[String], runs: &mut Vec<Run>) {
+      let mut current = run.font.as_str();
+      let mut content = String::new();
+      for c in run.content.chars() {
+         let font = if c.is_whitespace() || c.is_control() || c == SOFT_HYPHEN {
+            current
+         } else if self.has_glyph(&run.font, c) {
+            run.font.as_str()
+         } else {
+            fallbacks.iter().chain(self.fallbacks.iter()).map(|f| f.as_str())
+                     .find(|f| self.has_glyph(f, c)).unwrap_or(run.font.as_str())
+         };
+         if font != current && !content.is_empty() {
+            runs.push(run.part(current, content));
+            content = String::new();
+         }
+         current = font;
+         content.push(c);
+      }
+      runs.push(run.part(current, content));
+   }
+
+   ///The runs, alignment and wrapping of a Text or RichText Component
+   fn runs<'t>(&self, c: &'t Component, r: &Resolved, parent: &Space) -> Option<(Vec<Run>, &'t AlignUnit, WrapUnit)> {
+      use self::unicode_normalization::UnicodeNormalization;
+      let mut runs = Vec::new();
+      match *c {
+         Component::Text(ref text) => {
+            let run = Run { font: text.font.clone(), content: text.content.nfc().collect(), pixel_height: r.pixel_height,
+                            color: r.color, underline: false, strikethrough: false, line_height: r.line_height,
+                            letter_spacing: r.letter_spacing as f32, word_spacing: r.word_spacing as f32,
+                      direction: r.direction };
+            self.fall_back(run, &text.fallbacks, &mut runs);
+            Some((runs, &text.align, text.wrap))
+         }
+         Component::RichText(ref rich) => {
+            for span in rich.spans.iter() {
+               let run = Run {
+                  font: span.font.as_ref().unwrap_or(&rich.font).clone(),
+                  content: span.content.nfc().collect(),
+                  pixel_height: match span.scale {
+                     Some((scalar, ViewUnit::Center)) => { panic!("Invalid unit for scale: {:?}", (scalar, ViewUnit::Center)) }
+                     Some((scalar, ref u)) => { parent.length(scalar, u, parent.vertical_pct()).ceil() as usize }
+                     None => { r.pixel_height }
+                  },
+                  color: span.color.unwrap_or(r.color),
+                  underline: span.underline,
+                  strikethrough: span.strikethrough,
+                  line_height: r.line_height,
+                  letter_spacing: r.letter_spacing as f32,
+                  word_spacing: r.word_spacing as f32,
+                  direction: r.direction,
+               };
+               self.fall_back(run, &rich.fallbacks, &mut runs);
+            }
+            Some((runs, &rich.align, rich.wrap))
+         }
+         _ => { None }
+      }
+   }
+
+   ///The runs of a TextInput showing some chars, with the text an input method is composing underlined at a char index
+   fn input_runs(&self, input: &TextInput, r: &Resolved, shown: &[char], composing: Option<(usize, &str)>) -> Vec<Run> {
+      let run = Run { font: input.font.clone(), content: String::new(), pixel_height: r.pixel_height,
+                      color: r.color, underline: false, strikethrough: false, line_height: r.line_height,
+                      letter_spacing: r.letter_spacing as f32, word_spacing: r.word_spacing as f32,
+                      direction: r.direction };
+      let mut runs = Vec::new();
+      match composing {
+         Some((at, text)) => {
+            self.fall_back(run.part(&run.font, shown[..at].iter().collect()), &input.fallbacks, &mut runs);
+            let mut composed = run.part(&run.font, text.to_owned());
+            composed.underline = true;
+            self.fall_back(composed, &input.fallbacks, &mut runs);
+            self.fall_back(run.part(&run.font, shown[at..].iter().collect()), &input.fallbacks, &mut runs);
+         }
+         None => {
+            self.fall_back(run.part(&run.font, shown.iter().collect()), &input.fallbacks, &mut runs);
+         }
+      }
+      runs
+   }
+
+   ///The chars a TextInput shows for its value, masked if it is a password
+   fn shown(input: &TextInput, value: &[char]) -> Vec<char> {
+      match input.mask {
+         Some(mask) => { value.iter().map(|&c| if c == '\n' { c } else { mask }).collect() }
+         None => { value.to_vec() }
+      }
+   }
+
+   ///Break the value of a TextInput into lines, wrapping only if it is multiline
+   fn input_lines(&mut self, input: &TextInput, r: &Resolved, value: &[char], width: Option<usize>) -> Vec<Line> {
+      let shown = Renderer::shown(input, value);
+      let runs = self.input_runs(input, r, &shown, None);
+      let width = if input.multiline { width } else { None };
+      self.break_lines(&runs, WrapUnit::BreakWord, width)
+   }
+
+   ///The size of the value of a TextInput, with its padding, and the baseline of its first line
+   fn input_size(&mut self, input: &TextInput, r: &Resolved, events: &mut Events, width: Option<usize>) -> (usize, usize, f32) {
+      let value: Vec<char> = events.get(input.key.as_str()).chars().collect();
+      let (pl, pr) = r.paddings(true);
+      let (pt, pb) = r.paddings(false);
+      let lines = self.input_lines(input, r, &value, width.map(|w| (w as i32 - pl - pr).max(0) as usize));
+      let content = lines.iter().map(|l| l.width).fold(0.0, f32::max).ceil() as i32;
+      ((content + pl + pr).max(0) as usize, (lines.iter().map(|l| l.height as i32).sum::<i32>() + pt + pb).max(0) as usize,
+       lines.first().map(|l| l.ascent).unwrap_or(0.0))
+   }
+
+   ///Where the caret before a char index is drawn in lines laid out from the top: x, y and height
+   fn caret_at(lines: &[Line], index: usize) -> (f32, i32, usize) {
+      let mut start = 0;
+      let mut y = 0;
+      for line in lines.iter() {
+         if index < start + line.glyphs.len() {
+            return (line.carets()[index - start], y, line.height)
+         }
+         start += line.glyphs.len();
+         y += line.height as i32;
+      }
+      match lines.last() {
+         //after a trailing newline the caret starts the next line
+         Some(line) if line.glyphs.last().map(|g| g.1 == '\n').unwrap_or(false) => { (0.0, y, line.height) }
+         Some(line) => { (line.carets()[line.glyphs.len()], y - line.height as i32, line.height) }
+         None => { (0.0, 0, 0) }
+      }
+   }
+
+   ///The char index whose caret is nearest to a point in lines laid out from the top
+   fn index_at(lines: &[Line], x: f32, y: f32) -> usize {
+      let mut start = 0;
+      let mut top = 0.0;
+      for (li, line) in lines.iter().enumerate() {
+         let last = li + 1 == lines.len();
+         if y < top + line.height as f32 || last {
+            if line.bidi() {
+               //the nearest caret, short of a newline or the space a wrapped line broke at
+               let carets = line.carets();
+               let end = match line.glyphs.last() {
+                  Some(g) if g.1 == '\n' || (!last && g.1.is_whitespace()) => { line.glyphs.len() - 1 }
+                  _ => { line.glyphs.len() }
+               };
+               let nearest = (0..end + 1).min_by(|&a, &b| (carets[a] - x).abs().partial_cmp(&(carets[b] - x).abs()).expect("caret"));
+               return start + nearest.unwrap_or(0)
+            }
+            for (gi, g) in line.glyphs.iter().enumerate() {
+               if g.1 == '\n' || x < g.0 + g.3 / 2.0 { return start + gi }
+            }
+            //a wrapped line keeps its caret before the space it broke at
+            return match line.glyphs.last() {
+               Some(g) if !last && g.1.is_whitespace() => { start + line.glyphs.len() - 1 }
+               _ => { start + line.glyphs.len() }
+            }
+         }
+         start += line.glyphs.len();
+         top += line.height as f32;
+      }
+      start
+   }
+
+   ///Edit the value of the TextInput being edited with one Input
+   fn edit(&mut self, input: &TextInput, r: &Resolved, e: &mut Editing, value: &mut Vec<char>, i: &Input, width: usize) {
+      fn replace(e: &mut Editing, value: &mut Vec<char>, text: &[char]) {
+         let (first, last) = e.selection();
+         let tail = value.split_off(last);
+         value.truncate(first);
+         value.extend_from_slice(text);
+         value.extend(tail);
+         e.caret = first + text.len();
+         e.anchor = e.caret;
+      }
+      let (first, last) = e.selection();
+      match *i {
+         Input::Text(ref text) => {
+            e.composition = None;
+            let text: Vec<char> = text.chars().filter(|&c| !c.is_control() || (input.multiline && c == '\n')).collect();
+            replace(e, value, &text);
+         }
+         Input::Composition(ref text, cursor) => {
+            e.composition = if text.is_empty() { None } else { Some((text.clone(), cursor)) };
+         }
+         Input::KeyUp(_) | Input::MouseDown(_) | Input::MouseUp(_) | Input::Wheel(_, _) => {}
+         Input::KeyDown(ref key) => {
+            let (shift, ctrl) = (key.shift, key.ctrl || key.gui);
+            let caret = match key.name.as_str() {
+               "Backspace" => {
+                  if first == last && first > 0 { e.anchor = first - 1; e.caret = first; }
+                  replace(e, value, &[]); return
+               }
+               "Delete" => {
+                  if first == last && last < value.len() { e.anchor = last + 1; e.caret = last; }
+                  replace(e, value, &[]); return
+               }
+               "Return" | "Keypad Enter" if input.multiline => {
+                  replace(e, value, &['\n']); return
+               }
+               "A" if ctrl => {
+                  e.anchor = 0; e.caret = value.len(); return
+               }
+               "Left" if !shift && first != last => { first }
+               "Right" if !shift && first != last => { last }
+               "Left" => { e.caret.saturating_sub(1) }
+               "Right" => { (e.caret + 1).min(value.len()) }
+               "Home" | "End" | "Up" | "Down" => {
+                  let lines = self.input_lines(input, r, value, Some(width));
+                  let (x, y, height) = Renderer::caret_at(&lines, e.caret);
+                  let bottom: i32 = lines.iter().map(|l| l.height as i32).sum();
+                  match key.name.as_str() {
+                     "Home" => { Renderer::index_at(&lines, 0.0, y as f32) }
+                     "End" => { Renderer::index_at(&lines, ::std::f32::MAX, y as f32) }
+                     "Up" if y > 0 => { Renderer::index_at(&lines, x, (y - 1) as f32) }
+                     "Up" => { 0 }
+                     _ if y + (height as i32) < bottom => { Renderer::index_at(&lines, x, (y + height as i32) as f32) }
+                     _ => { value.len() }
+                  }
+               }
+               _ => { return }
+            };
+            e.caret = caret;
+            if !shift { e.anchor = caret; }
+         }
+      }
+   }
+
+   ///Edit a TextInput with the input of this frame and draw it, returning its bounding box
+   fn text_input<B>(&mut self, backend: &mut B, input: &TextInput, r: &Resolved, events: &mut Events, frame: &Frame,
+                    motion: &Motion, (pos_x, pos_y, width, height): (i32, i32, usize, usize)) -> (i32, i32, i32, i32)
+       where B: Backend {
+      let (pl, pr) = r.paddings(true);
+      let (pt, pb) = r.paddings(false);
+      let inner = ((width as i32 - pl - pr).max(0) as usize, (height as i32 - pt - pb).max(0) as usize);
+      let mut value: Vec<char> = events.get(input.key.as_str()).chars().collect();
+      let before = value.clone();
+
+      //the cursor inside the content box, scrolled along with a single line
+      let scroll = match events.editing { Some(ref e) if e.key == input.key => { e.scroll as f64 } _ => { 0.0 } };
+      let cursor = frame.cursor.map(|(x, y)| motion.invert(x as f64, y as f64));
+      let inside = cursor.map(|(x, y)| pos_x as f64 <= x && x < (pos_x + width as i32) as f64 &&
+                                       pos_y as f64 <= y && y < (pos_y + height as i32) as f64).unwrap_or(false);
+      if frame.click || (frame.held && events.editing.as_ref().map(|e| e.key == input.key && e.dragging).unwrap_or(false)) {
+         if inside || !frame.click {
+            let lines = self.input_lines(input, r, &value, Some(inner.0));
+            let (x, y) = cursor.unwrap_or((0.0, 0.0));
+            let top = Renderer::text_top(&lines, inner.1, r);
+            let index = Renderer::index_at(&lines, (x + scroll) as f32 - (pos_x + pl) as f32, (y as f32) - (pos_y + pt + top) as f32);
+            match events.editing {
+               Some(ref mut e) if e.key == input.key => {
+                  e.caret = index;
+                  if frame.click { e.anchor = index; e.composition = None; }
+                  e.dragging = true;
+               }
+               _ => {
+                  let mut e = Editing::new(input.key.as_str(), index);
+                  e.dragging = true;
+                  events.editing = Some(e);
+                  events.focus = Some(input.key.clone());
+               }
+            }
+         } else if events.editing.as_ref().map(|e| e.key == input.key).unwrap_or(false) {
+            events.editing = None;
+            events.focus = None;
+         }
+      }
+
+      //focus from Tab, or set by the view, starts editing at the end
+      if events.editing.is_none() && events.focus.as_ref() == Some(&input.key) {
+         events.editing = Some(Editing::new(input.key.as_str(), value.len()));
+      }
+      let mut editing = match events.editing { Some(ref e) if e.key == input.key => { events.editing.take() } _ => { None } };
+      if let Some(ref mut e) = editing {
+         if !frame.held { e.dragging = false; }
+         e.caret = e.caret.min(value.len());
+         e.anchor = e.anchor.min(value.len());
+         for i in frame.input.iter() {
+            self.edit(input, r, e, &mut value, i, inner.0);
+         }
+      }
+      if value != before {
+         events.set(input.key.as_str(), value.iter().cloned().collect::<String>().as_str());
+      }
+
+      //lay out what is shown, with any composition at the caret
+      let shown = Renderer::shown(input, &value);
+      let (runs, caret, selection) = match editing {
+         Some(ref e) => {
+            let (first, last) = e.selection();
+            match e.composition {
+               Some((ref text, cursor)) => {
+                  let composed = text.chars().count();
+                  let shift = |i: usize| if i > e.caret { i + composed } else { i };
+                  (self.input_runs(input, r, &shown, Some((e.caret, text.as_str()))), Some(e.caret + cursor.min(composed)),
+                   (shift(first), shift(last)))
+               }
+               None => { (self.input_runs(input, r, &shown, None), Some(e.caret), (first, last)) }
+            }
+         }
+         None => { (self.input_runs(input, r, &shown, None), None, (0, 0)) }
+      };
+      let lines = self.break_lines(&runs, WrapUnit::BreakWord, if input.multiline { Some(inner.0) } else { None });
+      let top = Renderer::text_top(&lines, inner.1, r);
+
+      //a single line scrolls just far enough to keep the caret in view
+      let mut scroll = 0;
+      if let (Some(ref mut e), Some(caret)) = (editing.as_mut(), caret) {
+         if !input.multiline {
+            let x = Renderer::caret_at(&lines, caret).0.round() as usize;
+            if x < e.scroll { e.scroll = x; }
+            if x + 1 > e.scroll + inner.0 { e.scroll = x + 1 - inner.0; }
+         } else {
+            e.scroll = 0;
+         }
+         scroll = e.scroll as i32;
+      }
+      let left = pos_x + pl - scroll;
+
+      //selection highlights the selected glyphs of each line, in as many pieces as bidi reordering splits them into
+      let mut start = 0;
+      let mut y = top;
+      for line in lines.iter() {
+         let mut spans = Vec::new();
+         let mut span: Option<(f32, f32)> = None;
+         for (gi, g) in line.visual() {
+            if selection.0 <= start + gi && start + gi < selection.1 {
+               span = Some((span.map(|s| s.0).unwrap_or(g.0), g.0 + g.3));
+            } else if let Some(s) = span.take() {
+               spans.push(s);
+            }
+         }
+         spans.extend(span);
+         for (from, to) in spans {
+            let x0 = (from.round() as i32 - scroll).max(0);
+            let x1 = (to.round() as i32 - scroll).min(inner.0 as i32).max(x0);
+            let (rect, rotation) = motion.place(Rect::new(pos_x + pl + x0, pos_y + pt + y, (x1 - x0) as u32, line.height as u32));
+            backend.draw_rect(rect, rotation, [r.color[0], r.color[1], r.color[2], r.color[3] * 0.3]);
+         }
+         start += line.glyphs.len();
+         y += line.height as i32;
+      }
+
+      //glyphs are clipped whole to the content box
+      let (positioned, decorations) = self.place(&runs, &lines, &AlignUnit::Left, inner, r);
+      for p in positioned.iter() {
+         let x = p.x - scroll;
+         if x < 0 || x + (p.width as i32) > inner.0 as i32 || p.y >= inner.1 as i32 { continue; }
+         let glyph = &self.glyphs.get(&p.key).expect("glyph").glyph;
+         let (rect, rotation) = motion.place(Rect::new(left + p.x, pos_y + pt + p.y, p.width as u32, p.height as u32));
+         backend.draw_glyph(p.key, glyph, rect.x, rect.y, rotation, runs[p.run].color);
+      }
+      for &(rect, ri) in decorations.iter() {
+         let (rect, rotation) = motion.place(Rect::new(left + rect.x, pos_y + pt + rect.y, rect.w, rect.h));
+         backend.draw_rect(rect, rotation, runs[ri].color);
+      }
+
+      //the caret blinks twice a second
+      if let Some(caret) = caret {
+         if (events.time_elapsed * 2.0) as i64 % 2 == 0 {
+            let (x, y, height) = Renderer::caret_at(&lines, caret);
+            let thickness = ((r.pixel_height as f32) / 16.0).ceil().max(1.0) as u32;
+            let (rect, rotation) = motion.place(Rect::new(left + x.round() as i32, pos_y + pt + top + y, thickness, height as u32));
+            backend.draw_rect(rect, rotation, r.color);
+         }
+      }
+
+      if editing.is_some() { events.editing = editing; }
+      (pos_x, pos_y, pos_x + width as i32, pos_y + height as i32)
+   }
+
+   ///Place the visible children of a Row or Column along its main axis
+   fn arrange(&mut self, group: &mut Group, events: &mut Events, inner: &Space, gap: usize) -> Vec<Option<Slot>> {
+      let horizontal = match group.layout {
+         Layout::Free => { return group.children.iter().map(|_| None).collect() }
+         Layout::Row => { true }
+         Layout::Column => { false }
+         Layout::Grid => { return self.arrange_grid(group, events, inner, gap) }
+      };
+      let (main, cross) = if horizontal { (inner.width, inner.height) } else { (inner.height, inner.width) };
+
+      //measure: (resolved modifiers, main axis basis, cross axis size if known, first baseline if aligned by it)
+      let mut items = Vec::new();
+      for child in group.children.iter_mut() {
+         if !Renderer::visible(child, events) {
+            items.push(None);
+            continue;
+         }
+         let r = Renderer::resolve(child, inner);
+         let natural = match self.runs(child, &r, inner) {
+            Some((runs, _, wrap)) => { Some(self.natural_size(&runs, wrap, r.max_lines)) }
+            None => { None }
+         };
+         let (w, h, baseline) = match (natural, &*child) {
+            (None, &Component::TextInput(ref input)) => {
+               let (nw, nh, ascent) = self.input_size(input, &r, events, r.width);
+               let baseline = if horizontal && r.vertical_align == VerticalAlignUnit::Baseline { Some((r.padding[0] + r.margin[0]) as f64 + ascent as f64) } else { None };
+               (Some(r.width.unwrap_or(nw)), Some(r.height.unwrap_or(nh)), baseline)
+            }
+            (Some((nw, nh, ascent)), _) => {
+               let (pl, pr) = r.paddings(true);
+               let (pt, pb) = r.paddings(false);
+               let baseline = if horizontal && r.vertical_align == VerticalAlignUnit::Baseline { Some((pt + r.margin[0]) as f64 + ascent as f64) } else { None };
+               (Some(r.width.unwrap_or((nw as i32 + pl + pr).max(0) as usize)),
+                Some(r.height.unwrap_or((nh as i32 + pt + pb).max(0) as usize)), baseline)
+            }
+            (None, _) => { (r.width, r.height, None) }
+         };
+         let (m, c) = if horizontal { (w, h) } else { (h, w) };
+         let basis = r.clamp(m.unwrap_or(0), horizontal) as f64;
+         items.push(Some((r, basis, c, baseline)));
+      }
+      //baseline aligned Text in a Row hangs its first line from the lowest first baseline among them
+      let baseline = items.iter().filter_map(|i| i.as_ref().and_then(|i| i.3)).fold(0.0, f64::max);
+
+      let count = items.iter().filter(|i| i.is_some()).count();
+      let gaps = if count > 1 { (gap * (count - 1)) as f64 } else { 0.0 };
+      let margins: f64 = items.iter().filter_map(|i| i.as_ref()).map(|i| { let (s, e) = i.0.margins(horizontal); (s + e) as f64 }).sum();
+      let used: f64 = items.iter().filter_map(|i| i.as_ref()).map(|i| i.1).sum();
+      let free = (main as f64) - used - margins - gaps;
+      let grow: f64 = items.iter().filter_map(|i| i.as_ref()).map(|i| i.0.grow).sum();
+      let shrink: f64 = items.iter().filter_map(|i| i.as_ref()).map(|i| i.0.shrink * i.1).sum();
+      let mut sizes: Vec<f64> = items.iter().map(|i| i.as_ref().map(|i| i.1).unwrap_or(0.0)).collect();
+      for (ii, i) in items.iter().enumerate() {
+         if let Some((ref r, basis, _, _)) = *i {
+            let size = if free > 0.0 && grow > 0.0 {
+               basis + free * r.grow / grow
+            } else if free < 0.0 && shrink > 0.0 {
+               (basis + free * r.shrink * basis / shrink).max(0.0)
+            } else {
+               basis
+            };
+            sizes[ii] = r.clamp(size.round() as usize, horizontal) as f64;
+         }
+      }
+      let free = (main as f64) - sizes.iter().sum::<f64>() - margins - gaps;
+
+      let (mut caret, spacing) = match group.justify {
+         JustifyUnit::Start => { (0.0, gap as f64) }
+         JustifyUnit::Center => { (free / 2.0, gap as f64) }
+         JustifyUnit::End => { (free, gap as f64) }
+         JustifyUnit::SpaceBetween => {
+            if count > 1 { (0.0, gap as f64 + free / ((count - 1) as f64)) } else { (0.0, gap as f64) }
+         }
+      };
+
+      let mut slots = Vec::new();
+      for (ii, i) in items.iter().enumerate() {
+         let (r, size, own_baseline) = match *i {
+            Some((ref r, _, size, own_baseline)) => { (r, size, own_baseline) }
+            None => { slots.push(None); continue; }
+         };
+         let (main_start, main_end) = r.margins(horizontal);
+         let (cross_start, cross_end) = r.margins(!horizontal);
+         let available = (cross as i32 - cross_start - cross_end).max(0);
+         let cross_size = r.clamp(size.unwrap_or(available as usize), !horizontal);
+         let cross_offset = match own_baseline {
+            Some(own_baseline) => { cross_start as f64 + baseline - own_baseline }
+            None => {
+               (cross_start as f64) + match group.cross {
+                  CrossUnit::Start | CrossUnit::Stretch => { 0.0 }
+                  CrossUnit::Center => { ((available as f64) - (cross_size as f64)) / 2.0 }
+                  CrossUnit::End => { (available as f64) - (cross_size as f64) }
+               }
+            }
+         };
+         caret += main_start as f64;
+         let main_size = sizes[ii] as usize;
+         let (x, y, width, height) = if horizontal {
+            (caret.round() as i32, cross_offset.round() as i32, main_size, cross_size)
+         } else {
+            (cross_offset.round() as i32, caret.round() as i32, cross_size, main_size)
+         };
+         slots.push(Some(Slot { x: x, y: y, width: width, height: height, area: (width, height) }));
+         caret += sizes[ii] + (main_end as f64) + spacing;
+      }
+      slots
+   }
+
+   ///Resolve the tracks of one Grid axis to (offset, size) pairs, adding implicit "1fr" tracks up to count
+   fn tracks(tracks: &[Track], count: usize, length: usize, gap: usize, inner: &Space, axis_pct: f64) -> Vec<(f64, f64)> {
+      let mut sizes = Vec::new();
+      let mut fractions = 0.0;
+      for ti in 0..count.max(tracks.len()) {
+         match tracks.get(ti) {
+            Some(&Track { scalar, unit: TrackUnit::Length(ref u) }) => {
+               sizes.push((inner.length(scalar, u, axis_pct).ceil(), 0.0));
+            }
+            Some(&Track { scalar, unit: TrackUnit::Fraction }) => {
+               sizes.push((0.0, scalar)); fractions += scalar;
+            }
+            None => {
+               sizes.push((0.0, 1.0)); fractions += 1.0;
+            }
+         }
+      }
+      let gaps = if sizes.len() > 1 { (gap * (sizes.len() - 1)) as f64 } else { 0.0 };
+      let fixed: f64 = sizes.iter().map(|s| s.0).sum();
+      let free = ((length as f64) - fixed - gaps).max(0.0);
+      let mut caret = 0.0;
+      let mut placed = Vec::new();
+      for &(size, fraction) in sizes.iter() {
+         let size = if fraction > 0.0 { free * fraction / fractions } else { size };
+         placed.push((caret, size));
+         caret += size + (gap as f64);
+      }
+      placed
+   }
+
+   ///Place the visible children of a Grid into its cells, filling free cells row by row
+   fn arrange_grid(&mut self, group: &mut Group, events: &mut Events, inner: &Space, gap: usize) -> Vec<Option<Slot>> {
+      let columns = group.columns.len().max(1);
+
+      //cells: (visible, column, row, column span, row span)
+      let mut taken: Vec<Vec<bool>> = Vec::new();
+      let mut cells = Vec::new();
+      let mut cursor = 0;
+      for child in group.children.iter_mut() {
+         if !Renderer::visible(child, events) {
+            cells.push((false, 0, 0, 0, 0));
+            continue;
+         }
+         let (mut cell, mut span) = (None, (1, 1));
+         for m in child.modifiers() {
+            match *m {
+               Modifier::Cell(ref s) => { cell = Some((s.column, s.row)); }
+               Modifier::Span(ref s) => { span = (s.columns.max(1), s.rows.max(1)); }
+               _ => {}
+            }
+         }
+         let span = (span.0.min(columns), span.1);
+         let (column, row) = match cell {
+            Some(c) => { c }
+            None => {
+               loop {
+                  let (column, row) = (cursor % columns, cursor / columns);
+                  let fits = column + span.0 <= columns &&
+                     (row..row+span.1).all(|r| (column..column+span.0).all(|c| !taken.get(r).map(|t| t[c]).unwrap_or(false)));
+                  if fits { break (column, row) }
+                  cursor += 1;
+               }
+            }
+         };
+         for r in row..row+span.1 {
+            while taken.len() <= r { taken.push(vec![false; columns]); }
+            for c in column..(column+span.0).min(columns) { taken[r][c] = true; }
+         }
+         cells.push((true, column, row, span.0, span.1));
+      }
+
+      let column_tracks = Renderer::tracks(&group.columns, columns, inner.width, gap, inner, inner.horizontal_pct());
+      let row_tracks = Renderer::tracks(&group.rows, taken.len(), inner.height, gap, inner, inner.vertical_pct());
+
+      let mut slots = Vec::new();
+      for (child, &(visible, column, row, column_span, row_span)) in group.children.iter_mut().zip(cells.iter()) {
+         if !visible { slots.push(None); continue; }
+         let span = |tracks: &Vec<(f64, f64)>, start: usize, count: usize| {
+            let first = tracks.get(start).map(|t| t.0).unwrap_or(0.0);
+            let last = tracks.get(start + count - 1).map(|t| t.0 + t.1).unwrap_or(first);
+            (first, last - first)
+         };
+         let (x, w) = span(&column_tracks, column, column_span);
+         let (y, h) = span(&row_tracks, row, row_span);
+         let cell = Space { width: w.round() as usize, height: h.round() as usize, motion: inner.motion };
+         let r = Renderer::resolve(child, &cell);
+         let area = ((cell.width as i32 - r.margin[1] - r.margin[3]).max(0) as usize,
+                     (cell.height as i32 - r.margin[0] - r.margin[2]).max(0) as usize);
+         slots.push(Some(Slot { x: x.round() as i32 + r.margin[3], y: y.round() as i32 + r.margin[0],
+                                width: r.clamp(r.width.unwrap_or(area.0), true),
+                                height: r.clamp(r.height.unwrap_or(area.1), false), area: area }));
+      }
+      slots
+   }
+
+   ///Render one Component, and any children, inside the box of its parent
+   fn component<B>(&mut self, backend: &mut B, c: &mut Component, events: &mut Events, frame: &Frame, parent: &Space, slot: Option<&Slot>)
+       where B: Backend {
+      let index = self.pointer.visited;
+      self.pointer.visited += 1;
+      if !Renderer::visible(c, events) {
+         return
+      }
+      for m in c.modifiers() {
+         match *m {
+            Modifier::State(ref s) => { events.state = s.val.clone(); }
+            _ => {}
+         }
+      }
+
+      let r = Renderer::resolve(c, parent);
+      let focus_key = Renderer::focus_key(c);
+      if let Some(ref key) = focus_key {
+         self.focus.order.push((r.tab_index, key.clone()));
+      }
+      let (width, height, mut pos_x, mut pos_y, area) = match slot {
+         Some(s) => { (s.width, s.height, s.x + r.pos_x, s.y + r.pos_y, s.area) }
+         None => {
+            let area = ((parent.width as i32 - r.margin[1] - r.margin[3]).max(0) as usize,
+                        (parent.height as i32 - r.margin[0] - r.margin[2]).max(0) as usize);
+            //a TextInput is as tall as its lines unless told otherwise
+            let width = r.clamp(r.width.unwrap_or(area.0), true);
+            let height = match (r.height, &*c) {
+               (None, &Component::TextInput(ref input)) => { self.input_size(input, &r, events, Some(width)).1 }
+               (height, _) => { height.unwrap_or(area.1) }
+            };
+            (width, r.clamp(height, false), r.pos_x + r.margin[3], r.pos_y + r.margin[0], area)
+         }
+      };
+      if r.center_x { pos_x += (((area.0 as f64) - (width as f64))*0.5).ceil() as i32; }
+      if r.center_y { pos_y += (((area.1 as f64) - (height as f64))*0.5).ceil() as i32; }
+      let color = r.color;
+      let shadow = r.shadow;
+      let border_width = r.border_width;
+
+      //rotate about the center of gravity, which is where the translation put us
+      let motion = parent.motion.compose(&Motion::rotate(r.radians, pos_x as f64, pos_y as f64));
+      pos_x -= (r.cog.0 * (width as f64)).ceil() as i32;
+      pos_y -= (r.cog.1 * (height as f64)).ceil() as i32;
+
+      if border_width > 0 {
+         let (rect, rotation) = motion.place(Rect::new(pos_x-(border_width as i32), pos_y-(border_width as i32),
+                                                       (width+2*border_width) as u32, (height+2*border_width) as u32));
+         backend.draw_rect(rect, rotation, r.border_color);
+      }
+
+      let bbox = match *c {
+         Component::Rectangle(_) => {
+            let (rect, rotation) = motion.place(Rect::new(pos_x, pos_y, width as u32, height as u32));
+            backend.draw_rect(rect, rotation, color);
+
+            (pos_x, pos_y, pos_x+(width as i32), pos_y+(height as i32))
+         }
+         Component::Image(ref image) => {
+            let (rect, rotation) = motion.place(Rect::new(pos_x, pos_y, width as u32, height as u32));
+            backend.draw_texture(image.name.as_str(), rect, rotation);
+
+            (pos_x, pos_y, pos_x+(width as i32), pos_y+(height as i32))
+         }
+         Component::Group(ref mut group) => {
+            let (pl, pr) = r.paddings(true);
+            let (pt, pb) = r.paddings(false);
+            let inner = Space {
+               width: (width as i32 - pl - pr).max(0) as usize,
+               height: (height as i32 - pt - pb).max(0) as usize,
+               motion: motion.compose(&Motion::translate((pos_x + pl) as f64, (pos_y + pt) as f64)),
+            };
+            let slots = self.arrange(group, events, &inner, r.gap);
+            for (child, slot) in group.children.iter_mut().zip(slots.iter()) {
+               self.component(backend, child, events, frame, &inner, slot.as_ref());
+            }
+
+            (pos_x, pos_y, pos_x+(width as i32), pos_y+(height as i32))
+         }
+         Component::TextInput(ref input) => {
+            self.text_input(backend, input, &r, events, frame, &motion, (pos_x, pos_y, width, height))
+         }
+         Component::Text(_) | Component::RichText(_) => {
+            let (runs, align, wrap) = self.runs(c, &r, parent).expect("text runs");
+            let (pl, pr) = r.paddings(true);
+            let (pt, pb) = r.paddings(false);
+            //only a known height clips, not the room left in the parent
+            let clip = slot.is_some() || r.height.is_some() || r.max_height.is_some();
+            let content = ((width as i32 - pl - pr).max(0) as usize, (height as i32 - pt - pb).max(0) as usize);
+            let (positioned, decorations) = self.layout_text(&runs, align, wrap, content, clip, &r);
+
+            let mut max_x = pos_x + pl;
+            let mut max_y = pos_y + pt;
+            for p in positioned.iter() {
+               let base_glyph = &self.glyphs.get(&p.key).expect("glyph").glyph;
+               let x = pos_x + pl + p.x;
+               let y = pos_y + pt + p.y;
+               let (shadow_box, sc) = shadow;
+               if shadow_box[0]<shadow_box[2] || shadow_box[1]<shadow_box[2] {
+                  for sx in (shadow_box[0]-1) .. shadow_box[2] {
+                  for sy in (shadow_box[1]-1) .. shadow_box[3] {
+                     let (rect, rotation) = motion.place(Rect::new(x + (sx as i32), y + (sy as i32), p.width as u32, p.height as u32));
+                     backend.draw_glyph(p.key, base_glyph, rect.x, rect.y, rotation, sc);
+                  }}
+               }
+               if x + (p.width as i32) > max_x { max_x = x + (p.width as i32) }
+               if y + (p.height as i32) > max_y { max_y = y + (p.height as i32) }
+               let (rect, rotation) = motion.place(Rect::new(x, y, p.width as u32, p.height as u32));
+               backend.draw_glyph(p.key, base_glyph, rect.x, rect.y, rotation, runs[p.run].color);
+            }
+            for &(rect, ri) in decorations.iter() {
+               let (rect, rotation) = motion.place(Rect::new(pos_x + pl + rect.x, pos_y + pt + rect.y, rect.w, rect.h));
+               backend.draw_rect(rect, rotation, runs[ri].color);
+            }
+
+            (pos_x, pos_y, max_x + pr, max_y + pb)
+         }
+      };
+      let hovered = match frame.cursor {
+         Some((cursor_x, cursor_y)) => {
+            let (cursor_x, cursor_y) = motion.invert(cursor_x as f64, cursor_y as f64);
+            bbox.0 as f64 <= cursor_x && cursor_x < bbox.2 as f64 &&
+            bbox.1 as f64 <= cursor_y && cursor_y < bbox.3 as f64
+         }
+         None => { false }
+      };
+      if frame.click && hovered && focus_key.is_some() {
+         self.focus.clicked = focus_key.clone();
+      }
+      //the cursor coming over a Component or leaving it is told from where it was last frame
+      let target = match Renderer::hover_key(c) { Some(key) => { Target::Key(key) } None => { Target::Place(index) } };
+      let edge = match (hovered, self.pointer.last_under.contains(&target)) {
+         (true, false) => { Some(Event::MouseEnter) }
+         (false, true) => { Some(Event::MouseLeave) }
+         _ => { None }
+      };
+      if hovered { self.pointer.under.push(target.clone()); }
+      let focused = focus_key.is_some() && focus_key == events.focus;
+
+      if let (true, Some((rgba, ring))) = (focused, r.focus_ring) {
+         let (x0, y0, x1) = (bbox.0 - ring as i32, bbox.1 - ring as i32, bbox.2 + ring as i32);
+         for &(x, y, w, h) in [(x0, y0, x1 - x0, ring as i32), (x0, bbox.3, x1 - x0, ring as i32),
+                               (x0, bbox.1, ring as i32, bbox.3 - bbox.1), (bbox.2, bbox.1, ring as i32, bbox.3 - bbox.1)].iter() {
+            let (rect, rotation) = motion.place(Rect::new(x, y, w.max(0) as u32, h.max(0) as u32));
+            backend.draw_rect(rect, rotation, rgba);
+         }
+      }
+
+      //Enter and Space activate a focused Component like a click, except a TextInput that types them
+      let activated: Vec<Key> = match *c {
+         Component::TextInput(_) => { Vec::new() }
+         _ if focused => { frame.input.iter().filter_map(|i| match *i {
+            Input::KeyDown(ref k) if !k.repeat && (k.name == "Return" || k.name == "Keypad Enter" || k.name == "Space") => { Some(k.clone()) }
+            _ => { None }
+         }).collect() }
+         _ => { Vec::new() }
+      };
+
+      let cursor = frame.cursor.map(|(x, y)| (x as f64, y as f64));
+      let detail = Detail {
+         event: Event::Always,
+         bbox: bbox,
+         cursor: cursor,
+         local: cursor.map(|(x, y)| { let (x, y) = motion.invert(x, y); (x - bbox.0 as f64, y - bbox.1 as f64) }),
+         mouse: None,
+         key: None,
+      };
+      let evs: Vec<_> = c.events().cloned().collect();
+      if focused {
+         Renderer::key_events(&evs, events, frame, &detail);
+      }
+      let draggable = evs.iter().any(|&(ref ev, _)| match *ev { Event::DragStart | Event::DragMove | Event::DragEnd => { true } _ => { false } });
+      if frame.click && hovered && draggable {
+         //the Component drawn last, over the others, takes the drag
+         self.pointer.grabbed = Some(target.clone());
+      }
+      self.mouse_events(&evs, events, frame, &target, hovered, edge, &detail);
+
+      //a click comes with the left press that made it, an activation with its key
+      let mut clicks = Vec::new();
+      if frame.click && hovered {
+         let (x, y) = cursor.unwrap_or((0.0, 0.0));
+         let presses = frame.input.iter().filter(|i| match **i { Input::MouseDown(_) => { true } _ => { false } })
+                                  .zip(self.pointer.presses.iter())
+                                  .filter(|&(i, _)| *i == Input::MouseDown(Button::Left))
+                                  .map(|(_, &n)| n).next().unwrap_or(1);
+         let mouse = Mouse { button: Some(Button::Left), x: x, y: y, delta: (0.0, 0.0), start: (x, y), clicks: presses };
+         clicks.push(Detail { event: Event::Clicked, mouse: Some(mouse), .. detail.clone() });
+      }
+      for k in activated {
+         clicks.push(Detail { event: Event::Clicked, key: Some(k), .. detail.clone() });
+      }
+      for (ev, f) in evs {
+         let fired = match ev {
+            Event::Always => { vec![Detail { event: Event::Always, .. detail.clone() }] }
+            Event::Hovered if hovered => { vec![Detail { event: Event::Hovered, .. detail.clone() }] }
+            Event::Clicked => { clicks.clone() }
+            Event::Focus if focus_key.is_some() && self.focus.changed.1 == focus_key => { vec![Detail { event: Event::Focus, .. detail.clone() }] }
+            Event::Blur if focus_key.is_some() && self.focus.changed.0 == focus_key => { vec![Detail { event: Event::Blur, .. detail.clone() }] }
+            _ => { Vec::new() }
+         };
+         for d in fired {
+            let mut callback = f.borrow_mut();
+            (&mut *callback)(events, &d);
+         }
+      }
+   }
+}
+
+///Lays out Text for Events::measure with the fonts and layout code of the Renderer, without drawing it
+pub struct Measurer {
+   renderer: Renderer,
+}
+impl Measurer {
+   ///Measure with fonts already decoded by a Renderer
+   pub fn new(fonts: Rc<Fonts>) -> Measurer {
+      Measurer {
+         renderer: Renderer {
+            fonts: fonts,
+            glyphs: GlyphCache::new(GLYPH_BUDGET),
+            fallbacks: Vec::new(),
+            focus: Focus::default(),
+            pointer: Pointer::default(),
+         }
+      }
+   }
+   ///Changes the fonts that every Text falls back to, after its own fallbacks
+   pub fn set_fallback_fonts(&mut self, fonts: &[&str]) {
+      self.renderer.set_fallback_fonts(fonts);
+   }
+   ///Lay out a Text or RichText as if it were appended to the View of a window of some size. None for any other Component.
+   pub fn measure(&mut self, c: &mut Component, (width, height): (usize, usize)) -> Option<TextMetrics> {
+      let window = Space { width: width, height: height, motion: Motion::identity() };
+      let r = Renderer::resolve(c, &window);
+      let (runs, _, wrap) = self.renderer.runs(c, &r, &window)?;
+      let area = ((width as i32 - r.margin[1] - r.margin[3]).max(0) as usize,
+                  (height as i32 - r.margin[0] - r.margin[2]).max(0) as usize);
+      let (pl, pr) = r.paddings(true);
+      let (pt, pb) = r.paddings(false);
+      let width = (r.clamp(r.width.unwrap_or(area.0), true) as i32 - pl - pr).max(0) as usize;
+      let height = (r.clamp(r.height.unwrap_or(area.1), false) as i32 - pt - pb).max(0) as usize;
+      let clip = r.height.is_some() || r.max_height.is_some();
+
+      let lines = self.renderer.break_lines(&runs, wrap, Some(width));
+      let lines = self.renderer.truncate(&runs, lines, (width, if clip { Some(height) } else { None }), &r);
+      self.renderer.glyphs.trim();
+      Some(TextMetrics {
+         width: lines.iter().map(|l| l.width as f64).fold(0.0, f64::max),
+         height: lines.iter().map(|l| l.height as f64).sum(),
+         lines: lines.iter().map(|l| l.width as f64).collect(),
+      })
+   }
+}
+
+///Read back the frame for any captures requested during it, before it is presented
+pub fn capture<B>(backend: &mut B, events: &mut Events)
+    where B: Backend {
+   if events.captures.len() == 0 { return }
+   events.capture_error = None;
+   let frame = match backend.capture() {
+      Some(frame) => { frame }
+      None => {
+         events.captures.clear();
+         events.capture_error = Some("Backend can't read back frames".to_owned());
+         return
+      }
+   };
+   for path in events.captures.drain(..) {
+      if path.len() > 0 {
+         if let Err(e) = frame.save(path.as_str()) {
+            events.capture_error = Some(format!("Could not save capture {}: {}", path, e));
+         }
+      }
+   }
+   events.captured = Some(frame);
+}
@@ -0,0 +1,103 @@
+use ::events::{Events};
+use ::view::{View};
+use ::window::{Window};
+
+extern crate image;
+use self::image::{RgbaImage, Rgba};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+///Renders Views offscreen and compares the frames against reference PNGs.
+///Set the LATTICE_BLESS environment variable to write new references instead of comparing.
+pub struct Snapshot {
+   width: u32,
+   height: u32,
+   tolerance: u8,
+   dir: PathBuf,
+}
+
+impl Snapshot {
+   ///Creates a new Snapshot harness with a fixed window size
+   pub fn new(width: u32, height: u32) -> Snapshot {
+      Snapshot {
+         width: width,
+         height: height,
+         tolerance: 2,
+         dir: Path::new("tests").join("snapshots"),
+      }
+   }
+   ///Changes the directory holding reference, actual and diff PNGs. Defaults to tests/snapshots.
+   pub fn set_dir(mut self, dir: &str) -> Snapshot {
+      self.dir = PathBuf::from(dir); self
+   }
+   ///Changes the largest difference allowed in any color channel. Defaults to 2.
+   pub fn set_tolerance(mut self, tolerance: u8) -> Snapshot {
+      self.tolerance = tolerance; self
+   }
+   ///Renders one frame for each time_elapsed and compares frame i against {name}_{i}.png.
+   ///Failing frames are written next to the reference as {name}_{i}.actual.png and {name}_{i}.diff.png.
+   pub fn check<F>(&self, window: &Window, name: &str, times: &[f64], mut cl: F) -> Result<(), String>
+       where F: FnMut(&mut Events) -> View {
+      let bless = env::var_os("LATTICE_BLESS").is_some();
+      if bless {
+         fs::create_dir_all(&self.dir).map_err(|e| format!("{:?}", e))?;
+      }
+      let mut headless = window.headless(self.width, self.height);
+      let mut failures = Vec::new();
+      for (fi, time_elapsed) in times.iter().enumerate() {
+         let actual = headless.render(*time_elapsed, &mut cl);
+         let reference_path = self.dir.join(format!("{}_{}.png", name, fi));
+         let actual_path = self.dir.join(format!("{}_{}.actual.png", name, fi));
+         let diff_path = self.dir.join(format!("{}_{}.diff.png", name, fi));
+         if bless {
+            actual.save(&reference_path).map_err(|e| format!("{:?}", e))?;
+            continue;
+         }
+         let reference = match image::open(&reference_path) {
+            Ok(r) => { r.to_rgba() }
+            Err(_) => {
+               actual.save(&actual_path).ok();
+               failures.push(format!("{}: missing reference, set LATTICE_BLESS to create it", reference_path.display()));
+               continue;
+            }
+         };
+         match diff(&reference, actual, self.tolerance) {
+            None => {
+               fs::remove_file(&actual_path).ok();
+               fs::remove_file(&diff_path).ok();
+            }
+            Some((mismatched, diff_image)) => {
+               actual.save(&actual_path).ok();
+               diff_image.save(&diff_path).ok();
+               failures.push(format!("{}: {} pixels differ, see {}", reference_path.display(), mismatched, diff_path.display()));
+            }
+         }
+      }
+      if failures.len() == 0 { Ok(()) } else { Err(failures.join("\n")) }
+   }
+}
+
+///Compare two images channel by channel. Returns the number of mismatched pixels and an image
+///with mismatches in red over a faded copy of the reference, if any pixel is out of tolerance.
+pub fn diff(reference: &RgbaImage, actual: &RgbaImage, tolerance: u8) -> Option<(usize, RgbaImage)> {
+   let (width, height) = reference.dimensions();
+   if actual.dimensions() != (width, height) {
+      let (aw, ah) = actual.dimensions();
+      return Some(((width.max(aw) * height.max(ah)) as usize, RgbaImage::from_pixel(aw, ah, Rgba { data: [255, 0, 0, 255] })));
+   }
+   let mut mismatched = 0;
+   let mut diff_image = RgbaImage::new(width, height);
+   for (x, y, r) in reference.enumerate_pixels() {
+      let a = actual.get_pixel(x, y);
+      let out = (0..4).any(|i| (r.data[i] as i32 - a.data[i] as i32).abs() > tolerance as i32);
+      if out {
+         mismatched += 1;
+         diff_image.put_pixel(x, y, Rgba { data: [255, 0, 0, 255] });
+      } else {
+         let grey = ((r.data[0] as u32 + r.data[1] as u32 + r.data[2] as u32) / 9) as u8;
+         diff_image.put_pixel(x, y, Rgba { data: [grey, grey, grey, 255] });
+      }
+   }
+   if mismatched == 0 { None } else { Some((mismatched, diff_image)) }
+}
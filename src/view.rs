@@ -1,9 +1,10 @@
-use ::events::Events;
+use ::events::{Events, Detail};
 use std::rc::Rc;
 use std::cell::RefCell;
 use std::slice::Iter;
+use std::ops::{Add, Sub};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 /// A typesafe unit for viewable components and modifiers
 pub enum ViewUnit {
    ///em
@@ -29,10 +30,16 @@ pub enum ViewUnit {
 
    ///Pixel: px
    Pixel,
+
+   ///Length expression, multiplied by the scalar: calc(50% - 2em)
+   Calc(Box<Length>),
 }
 impl ViewUnit {
    /// Convert a raw string to ViewUnit
    pub fn new(s: &str) -> ViewUnit {
+      if s.starts_with("calc(") || s.starts_with("min(") || s.starts_with("max(") || s.starts_with("clamp(") {
+         return ViewUnit::Calc(Box::new(Length::parse(s)))
+      }
       match s {
          "em" => { ViewUnit::Em }
          "%" => { ViewUnit::Percent }
@@ -52,6 +59,130 @@ impl<'a> Into<ViewUnit> for &'a str {
     }
 }
 
+
+#[derive(Debug, Clone)]
+/// A length expression mixing ViewUnits, such as calc(50% - 2em) or clamp(10em, 50%, 400px)
+pub enum Length {
+   ///scalar and unit
+   Unit(f64, ViewUnit),
+
+   ///a + b
+   Add(Box<Length>, Box<Length>),
+
+   ///a - b
+   Sub(Box<Length>, Box<Length>),
+
+   ///min(a, b)
+   Min(Box<Length>, Box<Length>),
+
+   ///max(a, b)
+   Max(Box<Length>, Box<Length>),
+
+   ///clamp(min, value, max)
+   Clamp(Box<Length>, Box<Length>, Box<Length>),
+}
+impl Length {
+   /// A single scalar and unit
+   pub fn new<T>(scalar: f64, unit: T) -> Length
+      where T: Into<ViewUnit> {
+      Length::Unit(scalar, unit.into())
+   }
+   /// The smaller of two lengths
+   pub fn min(self, other: Length) -> Length {
+      Length::Min(Box::new(self), Box::new(other))
+   }
+   /// The larger of two lengths
+   pub fn max(self, other: Length) -> Length {
+      Length::Max(Box::new(self), Box::new(other))
+   }
+   /// This length, but no smaller than lo and no larger than hi
+   pub fn clamp(self, lo: Length, hi: Length) -> Length {
+      Length::Clamp(Box::new(lo), Box::new(self), Box::new(hi))
+   }
+   /// Convert a raw string such as "calc(50% - 2em)" or "max(10em, 30%)" to a Length
+   pub fn parse(s: &str) -> Length {
+      let cs: Vec<char> = s.chars().collect();
+      let mut at = 0;
+      let l = Length::parse_sum(&cs, &mut at);
+      Length::skip_space(&cs, &mut at);
+      if at != cs.len() { panic!("Invalid Length: {}", s) }
+      l
+   }
+   fn skip_space(cs: &[char], at: &mut usize) {
+      while *at < cs.len() && cs[*at].is_whitespace() { *at += 1; }
+   }
+   fn expect(cs: &[char], at: &mut usize, c: char) {
+      Length::skip_space(cs, at);
+      if *at >= cs.len() || cs[*at] != c {
+         panic!("Invalid Length: expected '{}' in {}", c, cs.iter().collect::<String>())
+      }
+      *at += 1;
+   }
+   fn parse_sum(cs: &[char], at: &mut usize) -> Length {
+      let mut l = Length::parse_term(cs, at);
+      loop {
+         Length::skip_space(cs, at);
+         if *at >= cs.len() { return l }
+         match cs[*at] {
+            '+' => { *at += 1; l = l + Length::parse_term(cs, at); }
+            '-' => { *at += 1; l = l - Length::parse_term(cs, at); }
+            _ => { return l }
+         }
+      }
+   }
+   fn parse_term(cs: &[char], at: &mut usize) -> Length {
+      Length::skip_space(cs, at);
+      let start = *at;
+      while *at < cs.len() && cs[*at].is_alphabetic() { *at += 1; }
+      let name: String = cs[start..*at].iter().collect();
+      let paren = *at < cs.len() && cs[*at] == '(';
+      if paren {
+         *at += 1;
+         let a = Length::parse_sum(cs, at);
+         let l = match name.as_str() {
+            "" | "calc" => { a }
+            "min" => { Length::expect(cs, at, ','); a.min(Length::parse_sum(cs, at)) }
+            "max" => { Length::expect(cs, at, ','); a.max(Length::parse_sum(cs, at)) }
+            "clamp" => {
+               Length::expect(cs, at, ',');
+               let v = Length::parse_sum(cs, at);
+               Length::expect(cs, at, ',');
+               v.clamp(a, Length::parse_sum(cs, at))
+            }
+            f => { panic!("Invalid Length function: {}", f) }
+         };
+         Length::expect(cs, at, ')');
+         return l
+      }
+      *at = start;
+      if *at < cs.len() && cs[*at] == '-' { *at += 1; }
+      while *at < cs.len() && (cs[*at].is_digit(10) || cs[*at] == '.') { *at += 1; }
+      let scalar: String = cs[start..*at].iter().collect();
+      let scalar = scalar.parse::<f64>().expect(format!("Invalid Length scalar: {}", scalar).as_str());
+      let unit_start = *at;
+      while *at < cs.len() && !cs[*at].is_whitespace() && !",)+-".contains(cs[*at]) { *at += 1; }
+      let unit: String = cs[unit_start..*at].iter().collect();
+      Length::Unit(scalar, ViewUnit::new(unit.as_str()))
+   }
+}
+impl Add for Length {
+   type Output = Length;
+   fn add(self, other: Length) -> Length {
+      Length::Add(Box::new(self), Box::new(other))
+   }
+}
+impl Sub for Length {
+   type Output = Length;
+   fn sub(self, other: Length) -> Length {
+      Length::Sub(Box::new(self), Box::new(other))
+   }
+}
+impl Into<ViewUnit> for Length {
+    fn into(self) -> ViewUnit {
+       ViewUnit::Calc(Box::new(self))
+    }
+}
+
 #[derive(Debug)]
 /// A typesafe unit for component alignment in containers
 pub enum AlignUnit {
@@ -66,6 +197,12 @@ pub enum AlignUnit {
 
    ///justify
    Justify,
+
+   ///start: left in a left-to-right paragraph, right in a right-to-left one
+   Start,
+
+   ///end: right in a left-to-right paragraph, left in a right-to-left one
+   End,
 }
 impl AlignUnit {
    /// Convert a raw string to AlignUnit
@@ -75,6 +212,8 @@ impl AlignUnit {
          "center" => { AlignUnit::Center }
          "right" => { AlignUnit::Right }
          "justify" => { AlignUnit::Justify }
+         "start" => { AlignUnit::Start }
+         "end" => { AlignUnit::End }
          u => { panic!("Invalid Align Unit: {}", u) }
       }
    }
@@ -86,6 +225,134 @@ impl<'a> Into<AlignUnit> for &'a str {
 }
 
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// A typesafe unit for where Text may wrap onto a new line
+pub enum WrapUnit {
+   ///word: only at line break opportunities, long words overflow
+   Word,
+
+   ///break-word: at line break opportunities, and inside words that are longer than a line
+   BreakWord,
+
+   ///character: at any character
+   Character,
+}
+impl WrapUnit {
+   /// Convert a raw string to WrapUnit
+   pub fn new(s: &str) -> WrapUnit {
+      match s {
+         "word" => { WrapUnit::Word }
+         "break-word" => { WrapUnit::BreakWord }
+         "character" => { WrapUnit::Character }
+         u => { panic!("Invalid Wrap Unit: {}", u) }
+      }
+   }
+}
+impl<'a> Into<WrapUnit> for &'a str {
+    fn into(self) -> WrapUnit {
+       WrapUnit::new(self)
+    }
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// A typesafe unit for how Text shows the lines that do not fit
+pub enum OverflowUnit {
+   ///visible: lines past the Height are drawn anyway
+   Visible,
+
+   ///clip: only the lines that fit are drawn
+   Clip,
+
+   ///ellipsis: only the lines that fit are drawn, and the last one ends in an ellipsis
+   Ellipsis,
+
+   ///ellipsis-middle: the last line that fits is joined to the end of the text by an ellipsis
+   MiddleEllipsis,
+}
+impl OverflowUnit {
+   /// Convert a raw string to OverflowUnit
+   pub fn new(s: &str) -> OverflowUnit {
+      match s {
+         "visible" => { OverflowUnit::Visible }
+         "clip" => { OverflowUnit::Clip }
+         "ellipsis" => { OverflowUnit::Ellipsis }
+         "ellipsis-middle" => { OverflowUnit::MiddleEllipsis }
+         u => { panic!("Invalid Overflow Unit: {}", u) }
+      }
+   }
+}
+impl<'a> Into<OverflowUnit> for &'a str {
+    fn into(self) -> OverflowUnit {
+       OverflowUnit::new(self)
+    }
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// A typesafe unit for where the lines of Text sit vertically
+pub enum VerticalAlignUnit {
+   ///top: at the top of the box
+   Top,
+
+   ///middle: centered in the box
+   Middle,
+
+   ///bottom: at the bottom of the box
+   Bottom,
+
+   ///baseline: at the top of the box, and in a Row with the first baseline level with other baseline aligned children
+   Baseline,
+}
+impl VerticalAlignUnit {
+   /// Convert a raw string to VerticalAlignUnit
+   pub fn new(s: &str) -> VerticalAlignUnit {
+      match s {
+         "top" => { VerticalAlignUnit::Top }
+         "middle" => { VerticalAlignUnit::Middle }
+         "bottom" => { VerticalAlignUnit::Bottom }
+         "baseline" => { VerticalAlignUnit::Baseline }
+         u => { panic!("Invalid Vertical Align Unit: {}", u) }
+      }
+   }
+}
+impl<'a> Into<VerticalAlignUnit> for &'a str {
+    fn into(self) -> VerticalAlignUnit {
+       VerticalAlignUnit::new(self)
+    }
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// A typesafe unit for the direction of the paragraphs of Text
+pub enum DirectionUnit {
+   ///auto: each paragraph runs the way its first strong character does
+   Auto,
+
+   ///ltr: left-to-right
+   LeftToRight,
+
+   ///rtl: right-to-left
+   RightToLeft,
+}
+impl DirectionUnit {
+   /// Convert a raw string to DirectionUnit
+   pub fn new(s: &str) -> DirectionUnit {
+      match s {
+         "auto" => { DirectionUnit::Auto }
+         "ltr" => { DirectionUnit::LeftToRight }
+         "rtl" => { DirectionUnit::RightToLeft }
+         u => { panic!("Invalid Direction Unit: {}", u) }
+      }
+   }
+}
+impl<'a> Into<DirectionUnit> for &'a str {
+    fn into(self) -> DirectionUnit {
+       DirectionUnit::new(self)
+    }
+}
+
+
 #[derive(Debug)]
 /// A typesafe unit for Angle units
 pub enum AngleUnit {
@@ -116,6 +383,126 @@ impl<'a> Into<AngleUnit> for &'a str {
 }
 
 
+#[derive(Debug)]
+/// A typesafe unit for distributing free space along the main axis of a Row or Column
+pub enum JustifyUnit {
+   ///start
+   Start,
+
+   ///center
+   Center,
+
+   ///end
+   End,
+
+   ///space-between
+   SpaceBetween,
+}
+impl JustifyUnit {
+   /// Convert a raw string to JustifyUnit
+   pub fn new(s: &str) -> JustifyUnit {
+      match s {
+         "start" => { JustifyUnit::Start }
+         "center" => { JustifyUnit::Center }
+         "end" => { JustifyUnit::End }
+         "space-between" => { JustifyUnit::SpaceBetween }
+         u => { panic!("Invalid Justify Unit: {}", u) }
+      }
+   }
+}
+impl<'a> Into<JustifyUnit> for &'a str {
+    fn into(self) -> JustifyUnit {
+       JustifyUnit::new(self)
+    }
+}
+
+
+#[derive(Debug)]
+/// A typesafe unit for aligning children across the main axis of a Row or Column
+pub enum CrossUnit {
+   ///start
+   Start,
+
+   ///center
+   Center,
+
+   ///end
+   End,
+
+   ///stretch
+   Stretch,
+}
+impl CrossUnit {
+   /// Convert a raw string to CrossUnit
+   pub fn new(s: &str) -> CrossUnit {
+      match s {
+         "start" => { CrossUnit::Start }
+         "center" => { CrossUnit::Center }
+         "end" => { CrossUnit::End }
+         "stretch" => { CrossUnit::Stretch }
+         u => { panic!("Invalid Cross Unit: {}", u) }
+      }
+   }
+}
+impl<'a> Into<CrossUnit> for &'a str {
+    fn into(self) -> CrossUnit {
+       CrossUnit::new(self)
+    }
+}
+
+
+#[derive(Debug)]
+/// How a Group places its children
+pub enum Layout {
+   ///children are positioned only by their own Modifiers
+   Free,
+
+   ///children are placed left to right
+   Row,
+
+   ///children are placed top to bottom
+   Column,
+
+   ///children are placed into the cells of column and row tracks
+   Grid,
+}
+
+
+#[derive(Debug)]
+/// A typesafe unit for the size of a Grid track
+pub enum TrackUnit {
+   ///a fixed length
+   Length(ViewUnit),
+
+   ///fr, a share of the space left over by fixed tracks
+   Fraction,
+}
+impl TrackUnit {
+   /// Convert a raw string to TrackUnit
+   pub fn new(s: &str) -> TrackUnit {
+      match s {
+         "fr" => { TrackUnit::Fraction }
+         u => { TrackUnit::Length(ViewUnit::new(u)) }
+      }
+   }
+}
+impl<'a> Into<TrackUnit> for &'a str {
+    fn into(self) -> TrackUnit {
+       TrackUnit::new(self)
+    }
+}
+
+#[derive(Debug)]
+/// The size of one column or row of a Grid
+pub struct Track {
+   ///Track scalar
+   pub scalar: f64,
+
+   ///Track unit
+   pub unit: TrackUnit,
+}
+
+
 /// A Modifier to define the width of a Component
 pub struct Width {
    ///scalar
@@ -293,178 +680,739 @@ impl Conditional {
    }
 }
 
-/// A Component to describe an Image to be rendered
-pub struct Image {
-   ///Asset Name
-   pub name: String,
+/// A Modifier to define the space between children of a Row or Column
+pub struct Gap {
+   ///Gap scalar
+   pub scalar: f64,
 
-   ///Component Modifiers
-   pub modifiers: Vec<Modifier>,
+   ///Gap unit
+   pub unit: ViewUnit,
+}
+impl Gap {
+   ///Create a new Gap Modifier
+   pub fn new(scalar: f64, unit: ViewUnit) -> Modifier {
+      Modifier::Gap(Gap { scalar:scalar, unit:unit })
+   }
+}
 
-   ///Event Handlers
-   pub events: Vec<(Event, Rc<RefCell<FnMut(&mut Events)>>)>,
+/// A Modifier to define how much of the free space in a Row or Column a child takes
+pub struct Grow {
+   ///Grow factor
+   pub scalar: f64,
 }
-impl Image {
-   ///Create a new Image Component
-   pub fn new(name: &str) -> Component {
-      Component::Image(Image { name: name.to_owned(), modifiers:Vec::new(), events:Vec::new() })
+impl Grow {
+   ///Create a new Grow Modifier
+   pub fn new(scalar: f64) -> Modifier {
+      Modifier::Grow(Grow { scalar:scalar })
    }
 }
 
-/// A Component to describe Text to be rendered
-pub struct Text {
-   ///Text Content
-   pub content: String,
+/// A Modifier to define how much a child gives up when a Row or Column overflows
+pub struct Shrink {
+   ///Shrink factor
+   pub scalar: f64,
+}
+impl Shrink {
+   ///Create a new Shrink Modifier
+   pub fn new(scalar: f64) -> Modifier {
+      Modifier::Shrink(Shrink { scalar:scalar })
+   }
+}
 
-   ///Text Font
-   pub font: String,
+/// A Modifier to define the space between the edges of a Component and its content
+pub struct Padding {
+   ///Padding scalars: top, right, bottom, left
+   pub sides: [f64; 4],
 
-   ///Text Alignment
-   pub align: AlignUnit,
+   ///Padding unit
+   pub unit: ViewUnit,
+}
+impl Padding {
+   ///Create a new Padding Modifier
+   pub fn new(sides: [f64; 4], unit: ViewUnit) -> Modifier {
+      Modifier::Padding(Padding { sides:sides, unit:unit })
+   }
+}
 
-   ///Component Modifiers
-   pub modifiers: Vec<Modifier>,
+/// A Modifier to define the space around a Component, kept clear by its container
+pub struct Margin {
+   ///Margin scalars: top, right, bottom, left
+   pub sides: [f64; 4],
 
-   ///Event Handlers
-   pub events: Vec<(Event, Rc<RefCell<FnMut(&mut Events)>>)>,
+   ///Margin unit
+   pub unit: ViewUnit,
 }
-impl Text {
-   ///Create a new Text Component
-   pub fn new(font: &str, cs: &str) -> Component {
-      Component::Text(Text { font:font.to_owned(), content: cs.to_owned(),
-                             align: AlignUnit::Left, modifiers:Vec::new(), events:Vec::new() })
+impl Margin {
+   ///Create a new Margin Modifier
+   pub fn new(sides: [f64; 4], unit: ViewUnit) -> Modifier {
+      Modifier::Margin(Margin { sides:sides, unit:unit })
    }
 }
 
-/// A Component to describe a Rectangle to be rendered
-pub struct Rectangle {
-   ///Component Modifiers
-   pub modifiers: Vec<Modifier>,
+/// A Modifier to define the smallest width of a Component
+pub struct MinWidth {
+   ///Width scalar
+   pub scalar: f64,
 
-   ///Event Handlers
-   pub events: Vec<(Event, Rc<RefCell<FnMut(&mut Events)>>)>,
+   ///Width unit
+   pub unit: ViewUnit,
 }
-impl Rectangle {
-   ///Create a new Rectangle Component
-   pub fn new<T>(w: f64, wunit: T, h: f64, hunit: T) -> Component
-      where T: Into<ViewUnit> {
-      Component::Rectangle(Rectangle { modifiers:Vec::new(), events:Vec::new() })
-      .width(w, wunit.into())
-      .height(h, hunit.into())
+impl MinWidth {
+   ///Create a new MinWidth Modifier
+   pub fn new(scalar: f64, unit: ViewUnit) -> Modifier {
+      Modifier::MinWidth(MinWidth { scalar:scalar, unit:unit })
    }
 }
 
-macro_rules! push_event {
-   ($base: expr, $cls: ident, $fnx: ident) => {{
-      let ref mut m = $base;
-      m.push( (Event::$cls, Rc::new(RefCell::new($fnx))) );
-   }};
+/// A Modifier to define the largest width of a Component
+pub struct MaxWidth {
+   ///Width scalar
+   pub scalar: f64,
+
+   ///Width unit
+   pub unit: ViewUnit,
 }
-macro_rules! push_modifier {
-   ($base: expr, $cls: ident, ( $($arg:expr ,)* ) ) => {{
-      let ref mut m = $base;
-      m.push( $cls::new( $($arg),* ) );
-   }}
+impl MaxWidth {
+   ///Create a new MaxWidth Modifier
+   pub fn new(scalar: f64, unit: ViewUnit) -> Modifier {
+      Modifier::MaxWidth(MaxWidth { scalar:scalar, unit:unit })
+   }
 }
 
-///A renderable View Component
-pub enum Component {
-   ///Component::Image
-   Image(Image),
-
-   ///Component::Text
-   Text(Text),
+/// A Modifier to define the smallest height of a Component
+pub struct MinHeight {
+   ///Height scalar
+   pub scalar: f64,
 
-   ///Component::Rectangle
-   Rectangle(Rectangle),
+   ///Height unit
+   pub unit: ViewUnit,
 }
-impl Component {
-
-   ///Iterate over Modifiers of this Component
-   pub fn modifiers(&mut self) -> Iter<Modifier> {
-      match *self {
-         Component::Text(ref m) => { m.modifiers.iter() }
-         Component::Image(ref m) => { m.modifiers.iter() }
-         Component::Rectangle(ref m) => { m.modifiers.iter() }
-      }
+impl MinHeight {
+   ///Create a new MinHeight Modifier
+   pub fn new(scalar: f64, unit: ViewUnit) -> Modifier {
+      Modifier::MinHeight(MinHeight { scalar:scalar, unit:unit })
    }
+}
 
-   ///Add a Width Modifier to this Component
-   pub fn width<T>(mut self, scalar: f64, unit: T) -> Component
-      where T: Into<ViewUnit> {
-      match self {
-         Component::Text(ref mut m) => { push_modifier!(m.modifiers, Width, (scalar, unit.into(),)) }
-         Component::Image(ref mut m) => { push_modifier!(m.modifiers, Width, (scalar, unit.into(),)) }
-         Component::Rectangle(ref mut m) => { push_modifier!(m.modifiers, Width, (scalar, unit.into(),)) }
-      }; self
+/// A Modifier to define the largest height of a Component
+pub struct MaxHeight {
+   ///Height scalar
+   pub scalar: f64,
+
+   ///Height unit
+   pub unit: ViewUnit,
+}
+impl MaxHeight {
+   ///Create a new MaxHeight Modifier
+   pub fn new(scalar: f64, unit: ViewUnit) -> Modifier {
+      Modifier::MaxHeight(MaxHeight { scalar:scalar, unit:unit })
    }
+}
 
-   ///Add a Height Modifier to this Component
-   pub fn height<T>(mut self, scalar: f64, unit: T) -> Component
-      where T: Into<ViewUnit> {
-      match self {
-         Component::Text(ref mut m) => { push_modifier!(m.modifiers, Height, (scalar, unit.into(),)) }
-         Component::Image(ref mut m) => { push_modifier!(m.modifiers, Height, (scalar, unit.into(),)) }
-         Component::Rectangle(ref mut m) => { push_modifier!(m.modifiers, Height, (scalar, unit.into(),)) }
-      }; self
+/// A Modifier to define the most lines of Text that are drawn
+pub struct MaxLines {
+   ///Line count
+   pub lines: usize,
+}
+impl MaxLines {
+   ///Create a new MaxLines Modifier
+   pub fn new(lines: usize) -> Modifier {
+      Modifier::MaxLines(MaxLines { lines:lines })
    }
+}
 
-   ///Add an Angle Modifier to this Component
-   pub fn rotate<T>(mut self, scalar: f64, unit: T) -> Component
-      where T: Into<AngleUnit> {
-      match self {
-         Component::Text(ref mut m) => { push_modifier!(m.modifiers, Angle, (scalar, unit.into(),)) }
-         Component::Image(ref mut m) => { push_modifier!(m.modifiers, Angle, (scalar, unit.into(),)) }
-         Component::Rectangle(ref mut m) => { push_modifier!(m.modifiers, Angle, (scalar, unit.into(),)) }
-      }; self
+/// A Modifier to define how Text that does not fit its Height or MaxLines is truncated
+pub struct Overflow {
+   ///Overflow unit
+   pub unit: OverflowUnit,
+}
+impl Overflow {
+   ///Create a new Overflow Modifier
+   pub fn new(unit: OverflowUnit) -> Modifier {
+      Modifier::Overflow(Overflow { unit:unit })
    }
+}
 
-   ///Add a Center of Gravity Modifier to this Component
-   pub fn cog(mut self, x: f64, y: f64) -> Component {
+/// A Modifier to define the distance between the baselines of lines of Text
+pub struct LineHeight {
+   ///Line height scalar
+   pub scalar: f64,
+
+   ///Line height unit
+   pub unit: ViewUnit,
+}
+impl LineHeight {
+   ///Create a new LineHeight Modifier
+   pub fn new(scalar: f64, unit: ViewUnit) -> Modifier {
+      Modifier::LineHeight(LineHeight { scalar:scalar, unit:unit })
+   }
+}
+
+/// A Modifier to define the extra space after every character of Text
+pub struct LetterSpacing {
+   ///Spacing scalar
+   pub scalar: f64,
+
+   ///Spacing unit
+   pub unit: ViewUnit,
+}
+impl LetterSpacing {
+   ///Create a new LetterSpacing Modifier
+   pub fn new(scalar: f64, unit: ViewUnit) -> Modifier {
+      Modifier::LetterSpacing(LetterSpacing { scalar:scalar, unit:unit })
+   }
+}
+
+/// A Modifier to define the extra space after every space between the words of Text
+pub struct WordSpacing {
+   ///Spacing scalar
+   pub scalar: f64,
+
+   ///Spacing unit
+   pub unit: ViewUnit,
+}
+impl WordSpacing {
+   ///Create a new WordSpacing Modifier
+   pub fn new(scalar: f64, unit: ViewUnit) -> Modifier {
+      Modifier::WordSpacing(WordSpacing { scalar:scalar, unit:unit })
+   }
+}
+
+/// A Modifier to define where the lines of Text sit vertically in their box
+pub struct VerticalAlign {
+   ///Vertical align unit
+   pub unit: VerticalAlignUnit,
+}
+impl VerticalAlign {
+   ///Create a new VerticalAlign Modifier
+   pub fn new(unit: VerticalAlignUnit) -> Modifier {
+      Modifier::VerticalAlign(VerticalAlign { unit:unit })
+   }
+}
+
+/// A Modifier to set the direction of the paragraphs of Text
+pub struct Direction {
+   ///Direction unit
+   pub unit: DirectionUnit,
+}
+impl Direction {
+   ///Create a new Direction Modifier
+   pub fn new(unit: DirectionUnit) -> Modifier {
+      Modifier::Direction(Direction { unit:unit })
+   }
+}
+
+/// A Modifier to let a Component take keyboard focus, by a key that names it in Events.focus
+pub struct Focusable {
+   ///Key the Component is focused by
+   pub key: String,
+}
+impl Focusable {
+   ///Create a new Focusable Modifier
+   pub fn new(key: &str) -> Modifier {
+      Modifier::Focusable(Focusable { key:key.to_owned() })
+   }
+}
+
+/// A Modifier to name a Component in Events.hovered, and to tell it apart from the others when the View changes
+pub struct Hoverable {
+   ///Key the Component is hovered by
+   pub key: String,
+}
+impl Hoverable {
+   ///Create a new Hoverable Modifier
+   pub fn new(key: &str) -> Modifier {
+      Modifier::Hoverable(Hoverable { key:key.to_owned() })
+   }
+}
+
+/// A Modifier to define where a focusable Component comes in Tab order.
+/// Positive indexes come first in ascending order, then 0 in View order. Negative indexes are skipped by Tab.
+pub struct TabIndex {
+   ///Tab index
+   pub index: i32,
+}
+impl TabIndex {
+   ///Create a new TabIndex Modifier
+   pub fn new(index: i32) -> Modifier {
+      Modifier::TabIndex(TabIndex { index:index })
+   }
+}
+
+/// A Modifier to outline a Component while it has focus
+pub struct FocusRing {
+   ///rgba
+   pub rgba: [f64; 4],
+
+   ///scalar
+   pub scalar: f64,
+
+   ///unit
+   pub unit: ViewUnit,
+}
+impl FocusRing {
+   ///Create a new FocusRing Modifier
+   pub fn new(rgba: [f64; 4], scalar: f64, unit: ViewUnit) -> Modifier {
+      Modifier::FocusRing(FocusRing { rgba:rgba, scalar:scalar, unit:unit })
+   }
+}
+
+/// A Modifier to place a child of a Grid into a cell, counting from 0
+pub struct Cell {
+   ///Column index
+   pub column: usize,
+
+   ///Row index
+   pub row: usize,
+}
+impl Cell {
+   ///Create a new Cell Modifier
+   pub fn new(column: usize, row: usize) -> Modifier {
+      Modifier::Cell(Cell { column:column, row:row })
+   }
+}
+
+/// A Modifier to let a child of a Grid cover several cells
+pub struct Span {
+   ///Number of columns
+   pub columns: usize,
+
+   ///Number of rows
+   pub rows: usize,
+}
+impl Span {
+   ///Create a new Span Modifier
+   pub fn new(columns: usize, rows: usize) -> Modifier {
+      Modifier::Span(Span { columns:columns, rows:rows })
+   }
+}
+
+/// A Component to describe an Image to be rendered
+pub struct Image {
+   ///Asset Name
+   pub name: String,
+
+   ///Component Modifiers
+   pub modifiers: Vec<Modifier>,
+
+   ///Event Handlers
+   pub events: Vec<(Event, Rc<RefCell<FnMut(&mut Events, &Detail)>>)>,
+}
+impl Image {
+   ///Create a new Image Component
+   pub fn new(name: &str) -> Component {
+      Component::Image(Image { name: name.to_owned(), modifiers:Vec::new(), events:Vec::new() })
+   }
+}
+
+/// A Component to describe Text to be rendered
+pub struct Text {
+   ///Text Content
+   pub content: String,
+
+   ///Text Font
+   pub font: String,
+
+   ///Text Alignment
+   pub align: AlignUnit,
+
+   ///Text Wrapping
+   pub wrap: WrapUnit,
+
+   ///Fonts to draw characters from that the font lacks, in order
+   pub fallbacks: Vec<String>,
+
+   ///Component Modifiers
+   pub modifiers: Vec<Modifier>,
+
+   ///Event Handlers
+   pub events: Vec<(Event, Rc<RefCell<FnMut(&mut Events, &Detail)>>)>,
+}
+impl Text {
+   ///Create a new Text Component
+   pub fn new(font: &str, cs: &str) -> Component {
+      Component::Text(Text { font:font.to_owned(), content: cs.to_owned(),
+                             align: AlignUnit::Start, wrap: WrapUnit::BreakWord, fallbacks: Vec::new(),
+                             modifiers:Vec::new(), events:Vec::new() })
+   }
+}
+
+/// A run of text inside RichText, with its own font, size, color and decoration
+pub struct TextSpan {
+   ///Span Content
+   pub content: String,
+
+   ///Span Font, or the font of the RichText
+   pub font: Option<String>,
+
+   ///Span Scale, or the Scale of the RichText
+   pub scale: Option<(f64, ViewUnit)>,
+
+   ///Span Color, or the Color of the RichText
+   pub color: Option<[f64; 4]>,
+
+   ///Draw a line under the span
+   pub underline: bool,
+
+   ///Draw a line through the span
+   pub strikethrough: bool,
+}
+impl TextSpan {
+   ///Create a new TextSpan
+   pub fn new(cs: &str) -> TextSpan {
+      TextSpan { content: cs.to_owned(), font: None, scale: None, color: None, underline: false, strikethrough: false }
+   }
+   ///Changes the font of this span
+   pub fn font(mut self, font: &str) -> TextSpan {
+      self.font = Some(font.to_owned()); self
+   }
+   ///Changes the size of this span
+   pub fn scale<T>(mut self, scalar: f64, unit: T) -> TextSpan
+      where T: Into<ViewUnit> {
+      self.scale = Some((scalar, unit.into())); self
+   }
+   ///Changes the color of this span
+   pub fn color(mut self, rgba: [f64; 4]) -> TextSpan {
+      self.color = Some(rgba); self
+   }
+   ///Draws a line under this span
+   pub fn underline(mut self) -> TextSpan {
+      self.underline = true; self
+   }
+   ///Draws a line through this span
+   pub fn strikethrough(mut self) -> TextSpan {
+      self.strikethrough = true; self
+   }
+}
+
+/// A Component to describe a paragraph of TextSpans to be rendered
+pub struct RichText {
+   ///Text Spans
+   pub spans: Vec<TextSpan>,
+
+   ///Font of spans without their own
+   pub font: String,
+
+   ///Text Alignment
+   pub align: AlignUnit,
+
+   ///Text Wrapping
+   pub wrap: WrapUnit,
+
+   ///Fonts to draw characters from that the span fonts lack, in order
+   pub fallbacks: Vec<String>,
+
+   ///Component Modifiers
+   pub modifiers: Vec<Modifier>,
+
+   ///Event Handlers
+   pub events: Vec<(Event, Rc<RefCell<FnMut(&mut Events, &Detail)>>)>,
+}
+impl RichText {
+   ///Create a new RichText Component
+   pub fn new(font: &str) -> Component {
+      Component::RichText(RichText { spans: Vec::new(), font: font.to_owned(),
+                                     align: AlignUnit::Start, wrap: WrapUnit::BreakWord, fallbacks: Vec::new(),
+                                     modifiers:Vec::new(), events:Vec::new() })
+   }
+}
+
+/// A Component to describe an editable field of text, bound to a key of Events.keyval
+pub struct TextInput {
+   ///Key of the edited value in Events.keyval
+   pub key: String,
+
+   ///Text Font
+   pub font: String,
+
+   ///Whether Enter starts a new line, and lines wrap
+   pub multiline: bool,
+
+   ///Character drawn in place of every character of the value, to hide passwords
+   pub mask: Option<char>,
+
+   ///Fonts to draw characters from that the font lacks, in order
+   pub fallbacks: Vec<String>,
+
+   ///Component Modifiers
+   pub modifiers: Vec<Modifier>,
+
+   ///Event Handlers
+   pub events: Vec<(Event, Rc<RefCell<FnMut(&mut Events, &Detail)>>)>,
+}
+impl TextInput {
+   ///Create a new single line TextInput Component editing the value of a key
+   pub fn new(font: &str, key: &str) -> Component {
+      Component::TextInput(TextInput { key: key.to_owned(), font: font.to_owned(), multiline: false, mask: None,
+                                       fallbacks: Vec::new(), modifiers:Vec::new(), events:Vec::new() })
+   }
+}
+
+/// A Component to describe a Rectangle to be rendered
+pub struct Rectangle {
+   ///Component Modifiers
+   pub modifiers: Vec<Modifier>,
+
+   ///Event Handlers
+   pub events: Vec<(Event, Rc<RefCell<FnMut(&mut Events, &Detail)>>)>,
+}
+impl Rectangle {
+   ///Create a new Rectangle Component
+   pub fn new<T>(w: f64, wunit: T, h: f64, hunit: T) -> Component
+      where T: Into<ViewUnit> {
+      Component::Rectangle(Rectangle { modifiers:Vec::new(), events:Vec::new() })
+      .width(w, wunit.into())
+      .height(h, hunit.into())
+   }
+}
+
+/// A Component to describe a Group of Components sharing a coordinate space
+pub struct Group {
+   ///Child Components, positioned relative to this Group
+   pub children: Vec<Component>,
+
+   ///Group Layout
+   pub layout: Layout,
+
+   ///Main axis distribution of a Row or Column
+   pub justify: JustifyUnit,
+
+   ///Cross axis alignment of a Row or Column
+   pub cross: CrossUnit,
+
+   ///Column tracks of a Grid
+   pub columns: Vec<Track>,
+
+   ///Row tracks of a Grid, extended with "1fr" rows as needed
+   pub rows: Vec<Track>,
+
+   ///Component Modifiers
+   pub modifiers: Vec<Modifier>,
+
+   ///Event Handlers
+   pub events: Vec<(Event, Rc<RefCell<FnMut(&mut Events, &Detail)>>)>,
+}
+impl Group {
+   ///Create a new Group Component
+   pub fn new() -> Component {
+      Group::with_layout(Layout::Free)
+   }
+   ///Create a new Group Component placing its children left to right
+   pub fn row() -> Component {
+      Group::with_layout(Layout::Row)
+   }
+   ///Create a new Group Component placing its children top to bottom
+   pub fn column() -> Component {
+      Group::with_layout(Layout::Column)
+   }
+   ///Create a new Group Component placing its children into the cells of a grid
+   pub fn grid() -> Component {
+      Group::with_layout(Layout::Grid)
+   }
+   fn with_layout(layout: Layout) -> Component {
+      Component::Group(Group { children:Vec::new(), layout: layout, justify: JustifyUnit::Start, cross: CrossUnit::Stretch,
+                               columns: Vec::new(), rows: Vec::new(), modifiers:Vec::new(), events:Vec::new() })
+   }
+}
+
+macro_rules! push_event {
+   ($base: expr, $cls: ident, $fnx: ident) => {{
+      let ref mut m = $base;
+      let mut f = $fnx;
+      m.push( (Event::$cls, Rc::new(RefCell::new(move |e: &mut Events, _: &Detail| f(e)))) );
+   }};
+}
+macro_rules! push_listener {
+   ($base: expr, $cls: ident, $fnx: ident) => {{
+      let ref mut m = $base;
+      m.push( (Event::$cls, Rc::new(RefCell::new($fnx))) );
+   }};
+}
+macro_rules! push_modifier {
+   ($base: expr, $cls: ident, ( $($arg:expr ,)* ) ) => {{
+      let ref mut m = $base;
+      m.push( $cls::new( $($arg),* ) );
+   }}
+}
+
+///A renderable View Component
+pub enum Component {
+   ///Component::Image
+   Image(Image),
+
+   ///Component::Text
+   Text(Text),
+
+   ///Component::Rectangle
+   Rectangle(Rectangle),
+
+   ///Component::Group
+   Group(Group),
+
+   ///Component::RichText
+   RichText(RichText),
+
+   ///Component::TextInput
+   TextInput(TextInput),
+}
+impl Component {
+
+   ///Iterate over Modifiers of this Component
+   pub fn modifiers(&mut self) -> Iter<Modifier> {
+      match *self {
+         Component::Text(ref m) => { m.modifiers.iter() }
+         Component::Image(ref m) => { m.modifiers.iter() }
+         Component::Rectangle(ref m) => { m.modifiers.iter() }
+         Component::Group(ref m) => { m.modifiers.iter() }
+         Component::RichText(ref m) => { m.modifiers.iter() }
+         Component::TextInput(ref m) => { m.modifiers.iter() }
+      }
+   }
+
+   ///Iterate over Event Handlers of this Component
+   pub fn events(&mut self) -> Iter<(Event, Rc<RefCell<FnMut(&mut Events, &Detail)>>)> {
+      match *self {
+         Component::Text(ref m) => { m.events.iter() }
+         Component::Image(ref m) => { m.events.iter() }
+         Component::Rectangle(ref m) => { m.events.iter() }
+         Component::Group(ref m) => { m.events.iter() }
+         Component::RichText(ref m) => { m.events.iter() }
+         Component::TextInput(ref m) => { m.events.iter() }
+      }
+   }
+
+   fn modifiers_mut(&mut self) -> &mut Vec<Modifier> {
+      match *self {
+         Component::Text(ref mut m) => { &mut m.modifiers }
+         Component::Image(ref mut m) => { &mut m.modifiers }
+         Component::Rectangle(ref mut m) => { &mut m.modifiers }
+         Component::Group(ref mut m) => { &mut m.modifiers }
+         Component::RichText(ref mut m) => { &mut m.modifiers }
+         Component::TextInput(ref mut m) => { &mut m.modifiers }
+      }
+   }
+
+   fn events_mut(&mut self) -> &mut Vec<(Event, Rc<RefCell<FnMut(&mut Events, &Detail)>>)> {
+      match *self {
+         Component::Text(ref mut m) => { &mut m.events }
+         Component::Image(ref mut m) => { &mut m.events }
+         Component::Rectangle(ref mut m) => { &mut m.events }
+         Component::Group(ref mut m) => { &mut m.events }
+         Component::RichText(ref mut m) => { &mut m.events }
+         Component::TextInput(ref mut m) => { &mut m.events }
+      }
+   }
+
+   ///Add a child Component to this Group
+   pub fn append(mut self, c: Component) -> Component {
       match self {
-         Component::Text(ref mut m) => { push_modifier!(m.modifiers, CenterOfGravity, (x, y,)) }
-         Component::Image(ref mut m) => { push_modifier!(m.modifiers, CenterOfGravity, (x, y,)) }
-         Component::Rectangle(ref mut m) => { push_modifier!(m.modifiers, CenterOfGravity, (x, y,)) }
+         Component::Group(ref mut m) => { m.children.push(c); }
+         _ => {}
+      }; self
+   }
+
+   ///Add a TextSpan to this RichText
+   pub fn append_span(mut self, span: TextSpan) -> Component {
+      match self {
+         Component::RichText(ref mut m) => { m.spans.push(span); }
+         _ => {}
       }; self
    }
 
+   ///Add a Width Modifier to this Component
+   pub fn width<T>(mut self, scalar: f64, unit: T) -> Component
+      where T: Into<ViewUnit> {
+      push_modifier!(self.modifiers_mut(), Width, (scalar, unit.into(),)); self
+   }
+
+   ///Add a Height Modifier to this Component
+   pub fn height<T>(mut self, scalar: f64, unit: T) -> Component
+      where T: Into<ViewUnit> {
+      push_modifier!(self.modifiers_mut(), Height, (scalar, unit.into(),)); self
+   }
+
+   ///Add a MinWidth Modifier to this Component
+   pub fn min_width<T>(mut self, scalar: f64, unit: T) -> Component
+      where T: Into<ViewUnit> {
+      push_modifier!(self.modifiers_mut(), MinWidth, (scalar, unit.into(),)); self
+   }
+
+   ///Add a MaxWidth Modifier to this Component
+   pub fn max_width<T>(mut self, scalar: f64, unit: T) -> Component
+      where T: Into<ViewUnit> {
+      push_modifier!(self.modifiers_mut(), MaxWidth, (scalar, unit.into(),)); self
+   }
+
+   ///Add a MinHeight Modifier to this Component
+   pub fn min_height<T>(mut self, scalar: f64, unit: T) -> Component
+      where T: Into<ViewUnit> {
+      push_modifier!(self.modifiers_mut(), MinHeight, (scalar, unit.into(),)); self
+   }
+
+   ///Add a MaxHeight Modifier to this Component
+   pub fn max_height<T>(mut self, scalar: f64, unit: T) -> Component
+      where T: Into<ViewUnit> {
+      push_modifier!(self.modifiers_mut(), MaxHeight, (scalar, unit.into(),)); self
+   }
+
+   ///Add a Padding Modifier with the same value on every side to this Component
+   pub fn padding<T>(mut self, scalar: f64, unit: T) -> Component
+      where T: Into<ViewUnit> {
+      push_modifier!(self.modifiers_mut(), Padding, ([scalar; 4], unit.into(),)); self
+   }
+
+   ///Add a Padding Modifier to this Component, with sides ordered top, right, bottom, left.
+   ///Paddings stack, so sides in different units can be combined.
+   pub fn padding_sides<T>(mut self, sides: [f64; 4], unit: T) -> Component
+      where T: Into<ViewUnit> {
+      push_modifier!(self.modifiers_mut(), Padding, (sides, unit.into(),)); self
+   }
+
+   ///Add a Margin Modifier with the same value on every side to this Component
+   pub fn margin<T>(mut self, scalar: f64, unit: T) -> Component
+      where T: Into<ViewUnit> {
+      push_modifier!(self.modifiers_mut(), Margin, ([scalar; 4], unit.into(),)); self
+   }
+
+   ///Add a Margin Modifier to this Component, with sides ordered top, right, bottom, left.
+   ///Margins stack, so sides in different units can be combined.
+   pub fn margin_sides<T>(mut self, sides: [f64; 4], unit: T) -> Component
+      where T: Into<ViewUnit> {
+      push_modifier!(self.modifiers_mut(), Margin, (sides, unit.into(),)); self
+   }
+
+   ///Add an Angle Modifier to this Component
+   pub fn rotate<T>(mut self, scalar: f64, unit: T) -> Component
+      where T: Into<AngleUnit> {
+      push_modifier!(self.modifiers_mut(), Angle, (scalar, unit.into(),)); self
+   }
+
+   ///Add a Center of Gravity Modifier to this Component
+   pub fn cog(mut self, x: f64, y: f64) -> Component {
+      push_modifier!(self.modifiers_mut(), CenterOfGravity, (x, y,)); self
+   }
+
    ///Add a TranslateX Modifier to this Component
    pub fn translate_x<T>(mut self, scalar: f64, unit: T) -> Component
       where T: Into<ViewUnit> {
-      match self {
-         Component::Text(ref mut m) => { push_modifier!(m.modifiers, TranslateX, (scalar, unit.into(),)) }
-         Component::Image(ref mut m) => { push_modifier!(m.modifiers, TranslateX, (scalar, unit.into(),)) }
-         Component::Rectangle(ref mut m) => { push_modifier!(m.modifiers, TranslateX, (scalar, unit.into(),)) }
-      }; self
+      push_modifier!(self.modifiers_mut(), TranslateX, (scalar, unit.into(),)); self
    }
 
    ///Add a TranslateY Modifier to this Component
    pub fn translate_y<T>(mut self, scalar: f64, unit: T) -> Component
       where T: Into<ViewUnit> {
-      match self {
-         Component::Text(ref mut m) => { push_modifier!(m.modifiers, TranslateY, (scalar, unit.into(),)) }
-         Component::Image(ref mut m) => { push_modifier!(m.modifiers, TranslateY, (scalar, unit.into(),)) }
-         Component::Rectangle(ref mut m) => { push_modifier!(m.modifiers, TranslateY, (scalar, unit.into(),)) }
-      }; self
+      push_modifier!(self.modifiers_mut(), TranslateY, (scalar, unit.into(),)); self
    }
 
    ///Add a Color Modifier to this Component
    pub fn color(mut self, rgba: [f64; 4]) -> Component {
-      match self {
-         Component::Text(ref mut m) => { push_modifier!(m.modifiers, Color, (rgba,)) }
-         Component::Image(ref mut m) => { push_modifier!(m.modifiers, Color, (rgba,)) }
-         Component::Rectangle(ref mut m) => { push_modifier!(m.modifiers, Color, (rgba,)) }
-      }; self
+      push_modifier!(self.modifiers_mut(), Color, (rgba,)); self
    }
 
    ///Add a Scale Modifier to this Component
    pub fn scale<T>(mut self, scalar: f64, unit: T) -> Component
       where T: Into<ViewUnit> {
-      match self {
-         Component::Text(ref mut m) => { push_modifier!(m.modifiers, Scale, (scalar, unit.into(),)) }
-         Component::Image(ref mut m) => { push_modifier!(m.modifiers, Scale, (scalar, unit.into(),)) }
-         Component::Rectangle(ref mut m) => { push_modifier!(m.modifiers, Scale, (scalar, unit.into(),)) }
-      }; self
+      push_modifier!(self.modifiers_mut(), Scale, (scalar, unit.into(),)); self
    }
 
    ///Add an Align Modifier to this Component
@@ -472,89 +1420,355 @@ impl Component {
       where T: Into<AlignUnit> {
       match self {
          Component::Text(ref mut m) => { m.align = align.into(); }
+         Component::RichText(ref mut m) => { m.align = align.into(); }
          _ => {}
       }; self
    }
 
-   ///Add a Border Modifier to this Component
-   pub fn border<T>(mut self, clr: [f64; 4], scalar: f64, unit: T) -> Component
+   ///Add a Gap Modifier to this Component
+   pub fn gap<T>(mut self, scalar: f64, unit: T) -> Component
       where T: Into<ViewUnit> {
+      push_modifier!(self.modifiers_mut(), Gap, (scalar, unit.into(),)); self
+   }
+
+   ///Add a Grow Modifier to this Component
+   pub fn grow(mut self, scalar: f64) -> Component {
+      push_modifier!(self.modifiers_mut(), Grow, (scalar,)); self
+   }
+
+   ///Add a Shrink Modifier to this Component
+   pub fn shrink(mut self, scalar: f64) -> Component {
+      push_modifier!(self.modifiers_mut(), Shrink, (scalar,)); self
+   }
+
+   ///Set the main axis distribution of this Row or Column
+   pub fn justify<T>(mut self, justify: T) -> Component
+      where T: Into<JustifyUnit> {
       match self {
-         Component::Text(ref mut m) => { push_modifier!(m.modifiers, Border, (clr, scalar, unit.into(),)) }
-         Component::Image(ref mut m) => { push_modifier!(m.modifiers, Border, (clr, scalar, unit.into(),)) }
-         Component::Rectangle(ref mut m) => { push_modifier!(m.modifiers, Border, (clr, scalar, unit.into(),)) }
+         Component::Group(ref mut m) => { m.justify = justify.into(); }
+         _ => {}
       }; self
    }
 
-   ///Add a Shadow Modifier to this Component
-   pub fn shadow(mut self, d: [i64; 4], c: [f64; 4]) -> Component {
+   ///Set the cross axis alignment of this Row or Column
+   pub fn align_items<T>(mut self, cross: T) -> Component
+      where T: Into<CrossUnit> {
       match self {
-         Component::Text(ref mut m) => { push_modifier!(m.modifiers, Shadow, (d, c,)) }
-         Component::Image(ref mut m) => { push_modifier!(m.modifiers, Shadow, (d, c,)) }
-         Component::Rectangle(ref mut m) => { push_modifier!(m.modifiers, Shadow, (d, c,)) }
+         Component::Group(ref mut m) => { m.cross = cross.into(); }
+         _ => {}
       }; self
    }
 
-   ///Add a State Modifier to this Component
-   pub fn state(mut self, val: &str) -> Component {
+   ///Set the column tracks of this Grid
+   pub fn columns<T>(mut self, tracks: &[(f64, T)]) -> Component
+      where T: Into<TrackUnit> + Copy {
       match self {
-         Component::Text(ref mut m) => { push_modifier!(m.modifiers, State, (val,)); }
-         Component::Image(ref mut m) => { push_modifier!(m.modifiers, State, (val,)); }
-         Component::Rectangle(ref mut m) => { push_modifier!(m.modifiers, State, (val,)); }
+         Component::Group(ref mut m) => { m.columns = tracks.iter().map(|&(s, u)| Track { scalar: s, unit: u.into() }).collect(); }
+         _ => {}
       }; self
    }
 
-   ///Add a Condition Modifier to this Component
-   pub fn condition(mut self, key: &str, val: &str) -> Component {
+   ///Set the row tracks of this Grid
+   pub fn rows<T>(mut self, tracks: &[(f64, T)]) -> Component
+      where T: Into<TrackUnit> + Copy {
       match self {
-         Component::Text(ref mut m) => { push_modifier!(m.modifiers, Conditional, (key, val,)); }
-         Component::Image(ref mut m) => { push_modifier!(m.modifiers, Conditional, (key, val,)); }
-         Component::Rectangle(ref mut m) => { push_modifier!(m.modifiers, Conditional, (key, val,)); }
+         Component::Group(ref mut m) => { m.rows = tracks.iter().map(|&(s, u)| Track { scalar: s, unit: u.into() }).collect(); }
+         _ => {}
       }; self
    }
 
-   ///Add a Clicked event listener to this Component
-   pub fn clicked<F>(mut self, f: F) -> Component 
-          where F: 'static + FnMut(&mut Events) {
+   ///Add a Cell Modifier to this Component
+   pub fn cell(mut self, column: usize, row: usize) -> Component {
+      push_modifier!(self.modifiers_mut(), Cell, (column, row,)); self
+   }
+
+   ///Add a Span Modifier to this Component
+   pub fn span(mut self, columns: usize, rows: usize) -> Component {
+      push_modifier!(self.modifiers_mut(), Span, (columns, rows,)); self
+   }
+
+   ///Add a font to draw the characters from that the fonts of this Text lack. Fallbacks are tried in the order they were added.
+   pub fn fallback(mut self, font: &str) -> Component {
       match self {
-         Component::Text(ref mut m) => { push_event!(m.events, Clicked, f); }
-         Component::Image(ref mut m) => { push_event!(m.events, Clicked, f); }
-         Component::Rectangle(ref mut m) => { push_event!(m.events, Clicked, f); }
+         Component::Text(ref mut m) => { m.fallbacks.push(font.to_owned()); }
+         Component::RichText(ref mut m) => { m.fallbacks.push(font.to_owned()); }
+         Component::TextInput(ref mut m) => { m.fallbacks.push(font.to_owned()); }
+         _ => {}
       }; self
    }
 
-   ///Add a Hovered event listener to this Component
-   pub fn hovered<F>(mut self, f: F) -> Component 
-          where F: 'static + FnMut(&mut Events) {
+   ///Add a MaxLines Modifier to this Component
+   pub fn max_lines(mut self, lines: usize) -> Component {
+      push_modifier!(self.modifiers_mut(), MaxLines, (lines,)); self
+   }
+
+   ///Add an Overflow Modifier to this Component
+   pub fn overflow<T>(mut self, unit: T) -> Component
+      where T: Into<OverflowUnit> {
+      push_modifier!(self.modifiers_mut(), Overflow, (unit.into(),)); self
+   }
+
+   ///Add a LineHeight Modifier to this Component
+   pub fn line_height<T>(mut self, scalar: f64, unit: T) -> Component
+      where T: Into<ViewUnit> {
+      push_modifier!(self.modifiers_mut(), LineHeight, (scalar, unit.into(),)); self
+   }
+
+   ///Add a LetterSpacing Modifier to this Component
+   pub fn letter_spacing<T>(mut self, scalar: f64, unit: T) -> Component
+      where T: Into<ViewUnit> {
+      push_modifier!(self.modifiers_mut(), LetterSpacing, (scalar, unit.into(),)); self
+   }
+
+   ///Add a WordSpacing Modifier to this Component
+   pub fn word_spacing<T>(mut self, scalar: f64, unit: T) -> Component
+      where T: Into<ViewUnit> {
+      push_modifier!(self.modifiers_mut(), WordSpacing, (scalar, unit.into(),)); self
+   }
+
+   ///Add a VerticalAlign Modifier to this Component
+   pub fn vertical_align<T>(mut self, unit: T) -> Component
+      where T: Into<VerticalAlignUnit> {
+      push_modifier!(self.modifiers_mut(), VerticalAlign, (unit.into(),)); self
+   }
+
+   ///Add a Direction Modifier to this Component
+   pub fn direction<T>(mut self, unit: T) -> Component
+      where T: Into<DirectionUnit> {
+      push_modifier!(self.modifiers_mut(), Direction, (unit.into(),)); self
+   }
+
+   ///Let this TextInput hold several lines, started with Enter
+   pub fn multiline(mut self) -> Component {
       match self {
-         Component::Text(ref mut m) => { push_event!(m.events, Hovered, f); }
-         Component::Image(ref mut m) => { push_event!(m.events, Hovered, f); }
-         Component::Rectangle(ref mut m) => { push_event!(m.events, Hovered, f); }
+         Component::TextInput(ref mut m) => { m.multiline = true; }
+         _ => {}
       }; self
    }
 
+   ///Draw every character of this TextInput as a mask character, e.g. '*' for passwords
+   pub fn password(mut self, mask: char) -> Component {
+      match self {
+         Component::TextInput(ref mut m) => { m.mask = Some(mask); }
+         _ => {}
+      }; self
+   }
+
+   ///Set where this Text may wrap onto a new line
+   pub fn wrap<T>(mut self, wrap: T) -> Component
+      where T: Into<WrapUnit> {
+      match self {
+         Component::Text(ref mut m) => { m.wrap = wrap.into(); }
+         Component::RichText(ref mut m) => { m.wrap = wrap.into(); }
+         _ => {}
+      }; self
+   }
+
+   ///Add a Border Modifier to this Component
+   pub fn border<T>(mut self, clr: [f64; 4], scalar: f64, unit: T) -> Component
+      where T: Into<ViewUnit> {
+      push_modifier!(self.modifiers_mut(), Border, (clr, scalar, unit.into(),)); self
+   }
+
+   ///Add a Focusable Modifier to this Component. A TextInput is focusable by its key without one.
+   pub fn focusable(mut self, key: &str) -> Component {
+      push_modifier!(self.modifiers_mut(), Focusable, (key,)); self
+   }
+
+   ///Add a Hoverable Modifier to this Component. A focusable Component is hoverable by its focus key without one.
+   pub fn hoverable(mut self, key: &str) -> Component {
+      push_modifier!(self.modifiers_mut(), Hoverable, (key,)); self
+   }
+
+   ///Add a TabIndex Modifier to this Component
+   pub fn tab_index(mut self, index: i32) -> Component {
+      push_modifier!(self.modifiers_mut(), TabIndex, (index,)); self
+   }
+
+   ///Add a FocusRing Modifier to this Component
+   pub fn focus_ring<T>(mut self, clr: [f64; 4], scalar: f64, unit: T) -> Component
+      where T: Into<ViewUnit> {
+      push_modifier!(self.modifiers_mut(), FocusRing, (clr, scalar, unit.into(),)); self
+   }
+
+   ///Add a Shadow Modifier to this Component
+   pub fn shadow(mut self, d: [i64; 4], c: [f64; 4]) -> Component {
+      push_modifier!(self.modifiers_mut(), Shadow, (d, c,)); self
+   }
+
+   ///Add a State Modifier to this Component
+   pub fn state(mut self, val: &str) -> Component {
+      push_modifier!(self.modifiers_mut(), State, (val,)); self
+   }
+
+   ///Add a Condition Modifier to this Component
+   pub fn condition(mut self, key: &str, val: &str) -> Component {
+      push_modifier!(self.modifiers_mut(), Conditional, (key, val,)); self
+   }
+
+   ///Add a Clicked event listener to this Component, also called when Enter or Space activates it while it has focus
+   pub fn clicked<F>(mut self, f: F) -> Component 
+          where F: 'static + FnMut(&mut Events) {
+      push_event!(self.events_mut(), Clicked, f); self
+   }
+
+   ///Add a Hovered event listener to this Component, called every frame the cursor is over it
+   pub fn hovered<F>(mut self, f: F) -> Component 
+          where F: 'static + FnMut(&mut Events) {
+      push_event!(self.events_mut(), Hovered, f); self
+   }
+
    ///Add an Always event listener to this Component
    pub fn always<F>(mut self, f: F) -> Component 
           where F: 'static + FnMut(&mut Events) {
-      match self {
-         Component::Text(ref mut m) => { push_event!(m.events, Always, f); }
-         Component::Image(ref mut m) => { push_event!(m.events, Always, f); }
-         Component::Rectangle(ref mut m) => { push_event!(m.events, Always, f); }
-      }; self
+      push_event!(self.events_mut(), Always, f); self
+   }
+
+   ///Add a Focus event listener to this Component, called in the frame after it takes focus
+   pub fn focused<F>(mut self, f: F) -> Component
+          where F: 'static + FnMut(&mut Events) {
+      push_event!(self.events_mut(), Focus, f); self
+   }
+
+   ///Add a Blur event listener to this Component, called in the frame after it loses focus
+   pub fn blurred<F>(mut self, f: F) -> Component
+          where F: 'static + FnMut(&mut Events) {
+      push_event!(self.events_mut(), Blur, f); self
+   }
+
+   ///Add a KeyDown event listener to this Component, called with Detail.key while it has focus
+   pub fn key_down<F>(mut self, f: F) -> Component
+          where F: 'static + FnMut(&mut Events, &Detail) {
+      push_listener!(self.events_mut(), KeyDown, f); self
+   }
+
+   ///Add a KeyUp event listener to this Component, called with Detail.key while it has focus
+   pub fn key_up<F>(mut self, f: F) -> Component
+          where F: 'static + FnMut(&mut Events, &Detail) {
+      push_listener!(self.events_mut(), KeyUp, f); self
+   }
+
+   ///Add a KeyRepeat event listener to this Component, called with Detail.key while it has focus
+   pub fn key_repeat<F>(mut self, f: F) -> Component
+          where F: 'static + FnMut(&mut Events, &Detail) {
+      push_listener!(self.events_mut(), KeyRepeat, f); self
+   }
+
+   ///Add a listener for any Event to this Component, called with the Detail of what happened
+   pub fn on<F>(mut self, event: Event, f: F) -> Component
+          where F: 'static + FnMut(&mut Events, &Detail) {
+      self.events_mut().push( (event, Rc::new(RefCell::new(f))) ); self
+   }
+
+   ///Add a MouseDown event listener to this Component, called with Detail.mouse when any button is pressed over it
+   pub fn mouse_down<F>(mut self, f: F) -> Component
+          where F: 'static + FnMut(&mut Events, &Detail) {
+      push_listener!(self.events_mut(), MouseDown, f); self
+   }
+
+   ///Add a MouseUp event listener to this Component, called with Detail.mouse when any button is released over it
+   pub fn mouse_up<F>(mut self, f: F) -> Component
+          where F: 'static + FnMut(&mut Events, &Detail) {
+      push_listener!(self.events_mut(), MouseUp, f); self
+   }
+
+   ///Add a DoubleClick event listener to this Component, called with Detail.mouse when a button is pressed twice in quick succession over it
+   pub fn double_clicked<F>(mut self, f: F) -> Component
+          where F: 'static + FnMut(&mut Events, &Detail) {
+      push_listener!(self.events_mut(), DoubleClick, f); self
+   }
+
+   ///Add a Wheel event listener to this Component, called with Detail.mouse when the wheel turns over it
+   pub fn wheel<F>(mut self, f: F) -> Component
+          where F: 'static + FnMut(&mut Events, &Detail) {
+      push_listener!(self.events_mut(), Wheel, f); self
+   }
+
+   ///Add a MouseEnter event listener to this Component, called with Detail.mouse in the frame the cursor comes over it
+   pub fn mouse_enter<F>(mut self, f: F) -> Component
+          where F: 'static + FnMut(&mut Events, &Detail) {
+      push_listener!(self.events_mut(), MouseEnter, f); self
+   }
+
+   ///Add a MouseLeave event listener to this Component, called with Detail.mouse in the frame the cursor leaves it
+   pub fn mouse_leave<F>(mut self, f: F) -> Component
+          where F: 'static + FnMut(&mut Events, &Detail) {
+      push_listener!(self.events_mut(), MouseLeave, f); self
+   }
+
+   ///Add a DragStart event listener to this Component, called with Detail.mouse once the left button pressed on it moves a few pixels.
+   ///The drag follows the Component by its hover key, so the View can reorder while it goes on.
+   pub fn drag_start<F>(mut self, f: F) -> Component
+          where F: 'static + FnMut(&mut Events, &Detail) {
+      push_listener!(self.events_mut(), DragStart, f); self
+   }
+
+   ///Add a DragMove event listener to this Component, called with Detail.mouse every frame the cursor moves while it is dragged
+   pub fn drag_move<F>(mut self, f: F) -> Component
+          where F: 'static + FnMut(&mut Events, &Detail) {
+      push_listener!(self.events_mut(), DragMove, f); self
+   }
+
+   ///Add a DragEnd event listener to this Component, called with Detail.mouse when the left button is released to end its drag
+   pub fn drag_end<F>(mut self, f: F) -> Component
+          where F: 'static + FnMut(&mut Events, &Detail) {
+      push_listener!(self.events_mut(), DragEnd, f); self
    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 ///User Events that Components can subscribe to
 pub enum Event {
    ///Event::Clicked
    Clicked,
 
-   ///Event::Hovered
+   ///Event::Hovered, every frame the cursor is over the Component
    Hovered,
 
    ///Event::Always
    Always,
+
+   ///Event::Focus, the Component took focus
+   Focus,
+
+   ///Event::Blur, the Component lost focus
+   Blur,
+
+   ///Event::KeyDown, a key pressed while the Component has focus, or anywhere if subscribed on the View
+   KeyDown,
+
+   ///Event::KeyUp, a key released while the Component has focus, or anywhere if subscribed on the View
+   KeyUp,
+
+   ///Event::KeyRepeat, a held key repeated while the Component has focus, or anywhere if subscribed on the View
+   KeyRepeat,
+
+   ///Event::MouseDown, a mouse button pressed over the Component
+   MouseDown,
+
+   ///Event::MouseUp, a mouse button released over the Component
+   MouseUp,
+
+   ///Event::DoubleClick, a mouse button pressed twice in quick succession over the Component
+   DoubleClick,
+
+   ///Event::Wheel, the mouse wheel turned over the Component
+   Wheel,
+
+   ///Event::MouseEnter, the cursor came over the Component
+   MouseEnter,
+
+   ///Event::MouseLeave, the cursor left the Component
+   MouseLeave,
+
+   ///Event::DragStart, the Component began to be dragged with the left mouse button
+   DragStart,
+
+   ///Event::DragMove, the cursor moved while dragging the Component
+   DragMove,
+
+   ///Event::DragEnd, the left mouse button released to drop the Component
+   DragEnd,
 }
 
 ///Modifiers adjust the rendering qualities of Components
@@ -594,18 +1808,88 @@ pub enum Modifier {
 
    ///Modifier::State
    State(State),
+
+   ///Modifier::Gap
+   Gap(Gap),
+
+   ///Modifier::Grow
+   Grow(Grow),
+
+   ///Modifier::Shrink
+   Shrink(Shrink),
+
+   ///Modifier::Cell
+   Cell(Cell),
+
+   ///Modifier::Span
+   Span(Span),
+
+   ///Modifier::Padding
+   Padding(Padding),
+
+   ///Modifier::Margin
+   Margin(Margin),
+
+   ///Modifier::MinWidth
+   MinWidth(MinWidth),
+
+   ///Modifier::MaxWidth
+   MaxWidth(MaxWidth),
+
+   ///Modifier::MinHeight
+   MinHeight(MinHeight),
+
+   ///Modifier::MaxHeight
+   MaxHeight(MaxHeight),
+
+   ///Modifier::MaxLines
+   MaxLines(MaxLines),
+
+   ///Modifier::Overflow
+   Overflow(Overflow),
+
+   ///Modifier::LineHeight
+   LineHeight(LineHeight),
+
+   ///Modifier::LetterSpacing
+   LetterSpacing(LetterSpacing),
+
+   ///Modifier::WordSpacing
+   WordSpacing(WordSpacing),
+
+   ///Modifier::VerticalAlign
+   VerticalAlign(VerticalAlign),
+
+   ///Modifier::Direction
+   Direction(Direction),
+
+   ///Modifier::Focusable
+   Focusable(Focusable),
+
+   ///Modifier::Hoverable
+   Hoverable(Hoverable),
+
+   ///Modifier::TabIndex
+   TabIndex(TabIndex),
+
+   ///Modifier::FocusRing
+   FocusRing(FocusRing),
 }
 
 ///The render queue
 pub struct View {
    ///All scheduled components
    pub components: Vec<Component>,
+
+   ///Event listeners that don't belong to any Component
+   pub events: Vec<(Event, Rc<RefCell<FnMut(&mut Events, &Detail)>>)>,
 }
 impl View {
    ///A renderable view to contain all queued Components
    pub fn new() -> View {
       View {
-         components: Vec::new()
+         components: Vec::new(),
+         events: Vec::new(),
       }
    }
    ///Put a Component onto the render queue
@@ -613,4 +1897,48 @@ impl View {
       self.components.push( c );
       self
    }
+   ///Add a listener for a keyboard Event anywhere, called with the Detail of what happened
+   pub fn on<F>(&mut self, event: Event, f: F) -> &mut View
+          where F: 'static + FnMut(&mut Events, &Detail) {
+      self.events.push( (event, Rc::new(RefCell::new(f))) ); self
+   }
+   ///Add a KeyDown event listener for every key pressed, called with Detail.key
+   pub fn key_down<F>(&mut self, f: F) -> &mut View
+          where F: 'static + FnMut(&mut Events, &Detail) {
+      push_listener!(self.events, KeyDown, f); self
+   }
+   ///Add a KeyUp event listener for every key released, called with Detail.key
+   pub fn key_up<F>(&mut self, f: F) -> &mut View
+          where F: 'static + FnMut(&mut Events, &Detail) {
+      push_listener!(self.events, KeyUp, f); self
+   }
+   ///Add a KeyRepeat event listener for every held key repeated, called with Detail.key
+   pub fn key_repeat<F>(&mut self, f: F) -> &mut View
+          where F: 'static + FnMut(&mut Events, &Detail) {
+      push_listener!(self.events, KeyRepeat, f); self
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::{Length};
+
+   fn parsed(s: &str) -> String {
+      format!("{:?}", Length::parse(s))
+   }
+
+   #[test]
+   fn spaced_expressions() {
+      assert_eq!(parsed("calc(50% - 2em)"), parsed("50%-2em"));
+      assert_eq!(parsed("min(10em, 30%)"), format!("{:?}", Length::new(10.0, "em").min(Length::new(30.0, "%"))));
+   }
+
+   #[test]
+   fn unspaced_expressions() {
+      assert_eq!(parsed("calc(50%-2em)"), parsed("calc(50% - 2em)"));
+      assert_eq!(parsed("1em+2px"), format!("{:?}", Length::new(1.0, "em") + Length::new(2.0, "px")));
+      assert_eq!(parsed("calc(-1em-2px+3%)"), parsed("calc(-1em - 2px + 3%)"));
+      assert_eq!(parsed("clamp(1em,50%-2px,400px)"), parsed("clamp(1em, 50% - 2px, 400px)"));
+      assert_eq!(parsed("max(10em,30%)+5px"), parsed("max(10em, 30%) + 5px"));
+   }
 }
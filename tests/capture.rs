@@ -0,0 +1,58 @@
+extern crate Lattice;
+extern crate image;
+mod common;
+use Lattice::view::{View, Rectangle};
+use std::fs;
+
+fn view() -> View {
+   let mut v = View::new();
+   v.append(Rectangle::new(10.0, "px", 10.0, "px")
+           .color([1.0, 0.0, 0.0, 1.0])
+           .clicked(|e| e.capture_image()));
+   v
+}
+
+#[test]
+fn capture_image() {
+   let w = common::window();
+   let mut h = w.headless(40, 30);
+   h.render(0.0, |_| view());
+   assert!(h.events().captured.is_none());
+
+   h.set_cursor(5, 5);
+   h.click();
+   h.render(0.0, |_| view());
+   let captured = h.events().captured.clone().expect("captured frame");
+   assert_eq!(captured.dimensions(), (40, 30));
+   assert_eq!(captured.get_pixel(5, 5).data, [255, 0, 0, 255]);
+   assert_eq!(captured.get_pixel(20, 20).data, [0, 0, 0, 255]);
+   assert_eq!(h.events().capture_error, None);
+}
+
+#[test]
+fn capture_after() {
+   let path = std::env::temp_dir().join("lattice_capture_after.png");
+   let _ = fs::remove_file(&path);
+   let w = common::window().set_capture_after(2, path.to_str().expect("temp path"));
+   let mut h = w.headless(40, 30);
+   h.render(0.0, |_| view());
+   h.render(0.0, |_| view());
+   assert!(!path.exists());
+   h.render(0.0, |_| view());
+   let png = image::open(&path).expect("captured png").to_rgba();
+   assert_eq!(png.dimensions(), (40, 30));
+   assert_eq!(png.get_pixel(5, 5).data, [255, 0, 0, 255]);
+   fs::remove_file(&path).ok();
+}
+
+#[test]
+fn capture_error() {
+   let w = common::window();
+   let mut h = w.headless(40, 30);
+   h.render(0.0, |e| {
+      e.capture("/nonexistent/lattice/capture.png");
+      view()
+   });
+   assert!(h.events().capture_error.as_ref().map(|e| e.contains("/nonexistent/lattice/capture.png")).unwrap_or(false));
+   assert!(h.events().captured.is_some());
+}
@@ -0,0 +1,44 @@
+#![allow(dead_code)]
+use Lattice::window::{Window};
+use Lattice::events::{Events};
+
+pub const FONT: &'static str = "assets/Macondo-Regular.ttf";
+pub const RTL_FONT: &'static str = "assets/DejaVuSans.ttf";
+
+///A Window with the example font loaded under FONT
+pub fn window() -> Window {
+   window_with(&[FONT])
+}
+
+///A Window with the example font loaded once under each of the given names
+pub fn window_with(names: &[&str]) -> Window {
+   let font = include_bytes!("../../examples/assets/Macondo-Regular.ttf");
+   let mut w = Window::new("tests");
+   w.load_assets(names.iter().map(|&name| (name, font.to_vec())).collect());
+   w
+}
+
+///A Window with every example asset loaded, for the example scenes
+pub fn example_window() -> Window {
+   let mut w = window();
+   w.load_assets(vec![
+      ("assets/background.png", include_bytes!("../../examples/assets/background.png").to_vec()),
+      ("assets/handcloth.png", include_bytes!("../../examples/assets/handcloth.png").to_vec()),
+   ]);
+   w
+}
+
+///A Window with a font that has Hebrew and Arabic glyphs loaded under RTL_FONT
+pub fn rtl_window() -> Window {
+   let mut w = window();
+   w.load_assets(vec![
+      (RTL_FONT, include_bytes!("../assets/DejaVuSans.ttf").to_vec()),
+   ]);
+   w
+}
+
+///Append what happened to a keyval log, so a test can read it back after the frame
+pub fn log(e: &mut Events, entry: &str) {
+   let log = e.get("log");
+   e.set("log", if log.is_empty() { entry.to_owned() } else { log + ", " + entry }.as_str());
+}
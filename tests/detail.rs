@@ -0,0 +1,45 @@
+extern crate Lattice;
+mod common;
+use Lattice::view::{View, Rectangle, Event};
+use Lattice::events::{Events, Detail, Button};
+
+///Log everything a listener was told about its event
+fn log(e: &mut Events, d: &Detail) {
+   let entry = format!("{:?} {:?} {:?} {:?} {:?}", d.event, d.bbox, d.local,
+                       d.mouse.as_ref().and_then(|m| m.button), d.key.as_ref().map(|k| k.name.clone()));
+   common::log(e, &entry);
+}
+
+fn view() -> View {
+   let mut v = View::new();
+   v.on(Event::KeyDown, log);
+   v.append(Rectangle::new(40.0, "px", 20.0, "px")
+           .translate_x(30.0, "px")
+           .translate_y(10.0, "px")
+           .focusable("button")
+           .on(Event::Clicked, log)
+           .on(Event::Hovered, log)
+           .clicked(|e| e.set("clicked", "yes")));
+   v
+}
+
+#[test]
+fn component_detail() {
+   let w = common::window();
+   let mut h = w.headless(240, 120);
+   h.set_cursor(35, 15);
+   h.click();
+   h.render(0.0, |_| view());
+   assert_eq!(h.events().get("clicked"), "yes");
+   assert_eq!(h.events().get("log"),
+              format!("Clicked (30, 10, 70, 30) Some((5.0, 5.0)) {:?} None, Hovered (30, 10, 70, 30) Some((5.0, 5.0)) None None",
+                      Some(Button::Left)));
+
+   h.events().set("log", "");
+   h.set_cursor(200, 100);
+   h.press_key("Space");
+   h.render(0.0, |_| view());
+   assert_eq!(h.events().get("log"),
+              "KeyDown (0, 0, 240, 120) Some((200.0, 100.0)) None Some(\"Space\"), \
+               Clicked (30, 10, 70, 30) Some((170.0, 90.0)) None Some(\"Space\")");
+}
@@ -0,0 +1,89 @@
+extern crate Lattice;
+mod common;
+use Lattice::view::{View, Rectangle, TextInput};
+
+fn view() -> View {
+   let mut v = View::new();
+   v.append(Rectangle::new(40.0, "px", 40.0, "px")
+           .focusable("first")
+           .clicked(|e| common::log(e, "first clicked"))
+           .focused(|e| common::log(e, "first focused"))
+           .blurred(|e| common::log(e, "first blurred")));
+   v.append(Rectangle::new(40.0, "px", 40.0, "px")
+           .translate_x(50.0, "px")
+           .focusable("second")
+           .tab_index(1)
+           .clicked(|e| common::log(e, "second clicked")));
+   v.append(Rectangle::new(40.0, "px", 40.0, "px")
+           .translate_x(100.0, "px")
+           .focusable("skipped")
+           .tab_index(-1));
+   v.append(TextInput::new("assets/Macondo-Regular.ttf", "name")
+           .width(80.0, "px")
+           .translate_x(150.0, "px"));
+   v
+}
+
+#[test]
+fn tab_order() {
+   let w = common::window();
+   let mut h = w.headless(240, 120);
+   let mut order = Vec::new();
+   for _ in 0..4 {
+      h.press_key("Tab");
+      h.render(0.0, |_| view());
+      order.push(h.events().focus.clone().unwrap_or_default());
+   }
+   assert_eq!(order, vec!["second", "first", "name", "second"]);
+
+   h.press_key("Shift+Tab");
+   h.render(0.0, |_| view());
+   assert_eq!(h.events().focus, Some("name".to_owned()));
+   h.press_key("Shift+Tab");
+   h.render(0.0, |_| view());
+   assert_eq!(h.events().focus, Some("first".to_owned()));
+}
+
+#[test]
+fn click_focuses_and_tab_starts_editing() {
+   let w = common::window();
+   let mut h = w.headless(240, 120);
+   h.set_cursor(110, 10);
+   h.click();
+   h.render(0.0, |_| view());
+   assert_eq!(h.events().focus, Some("skipped".to_owned()));
+
+   h.press_key("Shift+Tab");
+   h.render(0.0, |_| view());
+   assert_eq!(h.events().focus, Some("name".to_owned()));
+   h.type_text("abc");
+   h.render(0.0, |_| view());
+   assert_eq!(h.events().get("name"), "abc");
+
+   h.set_cursor(235, 115);
+   h.click();
+   h.render(0.0, |_| view());
+   assert_eq!(h.events().focus, None);
+   assert!(h.events().editing.is_none());
+}
+
+#[test]
+fn activation_and_focus_events() {
+   let w = common::window();
+   let mut h = w.headless(240, 120);
+   h.events().focus = Some("first".to_owned());
+   h.press_key("Return");
+   h.render(0.0, |_| view());
+   assert_eq!(h.events().get("log"), "first clicked, first focused");
+
+   h.events().set("log", "");
+   h.press_key("Space");
+   h.press_key("Tab");
+   h.render(0.0, |_| view());
+   assert_eq!(h.events().get("log"), "first clicked");
+   assert_eq!(h.events().focus, Some("name".to_owned()));
+
+   h.events().set("log", "");
+   h.render(0.0, |_| view());
+   assert_eq!(h.events().get("log"), "first blurred");
+}
@@ -0,0 +1,72 @@
+extern crate Lattice;
+mod common;
+use Lattice::view::{View, Text};
+
+const FONTS: &'static [&'static str] = &["assets/first.ttf", "assets/second.ttf"];
+
+fn view() -> View {
+   let mut v = View::new();
+   v.append(Text::new("assets/first.ttf", "ab"));
+   v.append(Text::new("assets/second.ttf", "ab"));
+   v
+}
+
+#[test]
+fn keyed_by_font() {
+   let w = common::window_with(FONTS);
+   let mut h = w.headless(64, 64);
+   h.render(0.0, |_| view());
+   let first = h.events().glyphs;
+   //"a", "b" and the hyphen for soft hyphens, once for each font
+   assert_eq!(first.glyphs, 6);
+   h.render(0.0, |_| view());
+   let second = h.events().glyphs;
+   assert_eq!(second.misses, first.misses);
+   assert!(second.hits > first.hits);
+   assert_eq!(second.evictions, 0);
+}
+
+#[test]
+fn evicts_over_budget() {
+   let w = common::window_with(FONTS).set_glyph_budget(0);
+   let mut h = w.headless(64, 64);
+   h.render(0.0, |_| view());
+   let first = h.events().glyphs;
+   assert_eq!(first.glyphs, 0);
+   assert_eq!(first.bytes, 0);
+   assert_eq!(first.evictions, 6);
+   h.render(0.0, |_| view());
+   let second = h.events().glyphs;
+   assert_eq!(second.misses, 2 * first.misses);
+}
+
+#[test]
+fn fallback_only_for_missing_glyphs() {
+   let w = common::window_with(FONTS).set_fallback_fonts(&["assets/second.ttf"]);
+   let mut h = w.headless(64, 64);
+   h.render(0.0, |_| {
+      let mut v = View::new();
+      //neither font has the star, so it stays with the first font like the letters
+      v.append(Text::new("assets/first.ttf", "a\u{2605}b").fallback("assets/second.ttf"));
+      v
+   });
+   //"a", "b", the star and the hyphen, all from the first font
+   assert_eq!(h.events().glyphs.glyphs, 4);
+}
+
+#[test]
+fn composed_glyphs_never_cached() {
+   let w = common::window_with(FONTS).set_glyph_budget(0);
+   let mut h = w.headless(64, 64);
+   //"e" and a combining acute compose to "\u{E9}", which no earlier frame has cached
+   h.render(0.0, |e| {
+      let decomposed = e.measure(&mut Text::new("assets/first.ttf", "e\u{301}")).expect("Text");
+      let composed = e.measure(&mut Text::new("assets/first.ttf", "\u{E9}")).expect("Text");
+      assert_eq!(decomposed, composed);
+      assert!(composed.width > 0.0);
+      let mut v = View::new();
+      v.append(Text::new("assets/first.ttf", "cafe\u{301} A\u{30A}"));
+      v
+   });
+   assert_eq!(h.events().glyphs.glyphs, 0);
+}
@@ -0,0 +1,65 @@
+extern crate Lattice;
+mod common;
+use Lattice::view::{View, Rectangle, TextInput};
+use Lattice::events::{Events, Detail};
+
+///Log the key a listener was called with
+fn log(e: &mut Events, d: &Detail, what: &str) {
+   let key = d.key.clone().expect("key");
+   let entry = format!("{} {} {}{}{}", what, key.name, key.code, if key.shift { " shift" } else { "" }, if key.ctrl { " ctrl" } else { "" });
+   common::log(e, &entry);
+}
+
+fn view() -> View {
+   let mut v = View::new();
+   v.key_down(|e, d| log(e, d, "down"))
+    .key_repeat(|e, d| log(e, d, "repeat"))
+    .key_up(|e, d| log(e, d, "up"));
+   v
+}
+
+#[test]
+fn global_listeners() {
+   let w = common::window();
+   let mut h = w.headless(240, 120);
+   h.key_down("Shift+A");
+   h.repeat_key("Shift+A");
+   h.key_up("A");
+   h.press_key("Ctrl+Left");
+   h.render(0.0, |_| view());
+   assert_eq!(h.events().get("log"),
+              "down A 97 shift, repeat A 97 shift, up A 97, down Left 1073741904 ctrl, up Left 1073741904 ctrl");
+}
+
+#[test]
+fn focused_listeners() {
+   fn view() -> View {
+      let mut v = View::new();
+      v.append(TextInput::new("assets/Macondo-Regular.ttf", "name")
+              .width(100.0, "px")
+              .key_down(|e, d| log(e, d, "input")));
+      v.append(Rectangle::new(50.0, "px", 50.0, "px")
+              .translate_x(150.0, "px")
+              .key_down(|e, d| log(e, d, "rectangle")));
+      v
+   }
+   let w = common::window();
+   let mut h = w.headless(240, 120);
+   h.press_key("Space");
+   h.render(0.0, |_| view());
+   assert_eq!(h.events().get("log"), "");
+
+   h.set_cursor(10, 10);
+   h.click();
+   h.press_key("Return");
+   h.render(0.0, |_| view());
+   assert_eq!(h.events().get("log"), "input Return 13");
+}
+
+#[test]
+#[should_panic(expected = "Invalid key name: NotAKey")]
+fn unknown_key_name() {
+   let w = common::window();
+   let mut h = w.headless(240, 120);
+   h.press_key("NotAKey");
+}
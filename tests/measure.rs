@@ -0,0 +1,69 @@
+extern crate Lattice;
+mod common;
+use Lattice::view::{View, Text, RichText, TextSpan, Rectangle, Image, Group, TextInput};
+use Lattice::events::{Events, TextMetrics};
+use common::{FONT};
+
+const PARAGRAPH: &'static str = "The quick brown fox jumps over the lazy dog";
+
+#[test]
+fn wrapped_lines() {
+   let w = common::window();
+   let mut h = w.headless(400, 300);
+   let mut narrow = TextMetrics::default();
+   let mut wide = TextMetrics::default();
+   let mut truncated = TextMetrics::default();
+   h.render(0.0, |e| {
+      assert_eq!(e.window, (400, 300));
+      narrow = e.measure(&mut Text::new(FONT, PARAGRAPH).width(120.0, "px")).expect("Text");
+      wide = e.measure(&mut Text::new(FONT, PARAGRAPH)).expect("Text");
+      truncated = e.measure(&mut Text::new(FONT, PARAGRAPH).width(120.0, "px").max_lines(2)).expect("Text");
+      View::new()
+   });
+
+   assert!(narrow.lines.len() > 2);
+   assert!(narrow.lines.iter().all(|&l| l > 0.0 && l <= 120.0));
+   assert_eq!(narrow.width, narrow.lines.iter().cloned().fold(0.0, f64::max));
+
+   assert_eq!(wide.lines.len(), 1);
+   assert_eq!(wide.width, wide.lines[0]);
+   assert!(wide.width > narrow.width);
+   assert!(narrow.height > wide.height);
+
+   assert_eq!(truncated.lines.len(), 2);
+   assert_eq!(truncated.height * narrow.lines.len() as f64, narrow.height * 2.0);
+}
+
+#[test]
+fn measured_size_fits_layout() {
+   let w = common::window();
+   let mut h = w.headless(400, 300);
+   let mut m = TextMetrics::default();
+   h.render(0.0, |e| {
+      m = e.measure(&mut Text::new(FONT, PARAGRAPH).width(120.0, "px")).expect("Text");
+      View::new()
+   });
+   //a Text given its measured size, rounded up to whole pixels, lays out on the same lines
+   let mut again = TextMetrics::default();
+   h.render(0.0, |e| {
+      again = e.measure(&mut Text::new(FONT, PARAGRAPH).width(m.width.ceil(), "px").height(m.height.ceil(), "px")).expect("Text");
+      View::new()
+   });
+   assert_eq!(again, m);
+}
+
+#[test]
+fn only_text_measures() {
+   let w = common::window();
+   let mut h = w.headless(400, 300);
+   h.render(0.0, |e| {
+      assert!(e.measure(&mut RichText::new(FONT).append_span(TextSpan::new(PARAGRAPH))).is_some());
+      assert_eq!(e.measure(&mut Rectangle::new(10.0, "px", 10.0, "px")), None);
+      assert_eq!(e.measure(&mut Image::new("assets/missing.png")), None);
+      assert_eq!(e.measure(&mut Group::new()), None);
+      assert_eq!(e.measure(&mut TextInput::new(FONT, "name")), None);
+      View::new()
+   });
+   //Events made without fonts have nothing to measure with
+   assert_eq!(Events::new().measure(&mut Text::new(FONT, PARAGRAPH)), None);
+}
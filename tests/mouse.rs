@@ -0,0 +1,182 @@
+extern crate Lattice;
+mod common;
+use Lattice::view::{View, Rectangle, Component};
+use Lattice::events::{Events, Detail};
+
+///Log the mouse event a listener was called with
+fn log(e: &mut Events, d: &Detail, what: &str) {
+   let mouse = d.mouse.clone().expect("mouse");
+   let entry = match mouse.button {
+      Some(button) => { format!("{} {:?} {} {}", what, button, mouse.x, mouse.y) }
+      None => { format!("{} {} {}", what, mouse.delta.0, mouse.delta.1) }
+   };
+   common::log(e, &entry);
+}
+
+fn view() -> View {
+   let mut v = View::new();
+   v.append(Rectangle::new(100.0, "px", 100.0, "px")
+           .mouse_down(|e, d| log(e, d, "down"))
+           .mouse_up(|e, d| log(e, d, "up"))
+           .double_clicked(|e, d| log(e, d, "double"))
+           .wheel(|e, d| log(e, d, "wheel")));
+   v
+}
+
+#[test]
+fn buttons_and_wheel() {
+   let w = common::window();
+   let mut h = w.headless(240, 120);
+   h.set_cursor(10, 20);
+   h.mouse_down("right");
+   h.mouse_up("right");
+   h.mouse_down("middle");
+   h.scroll(0.0, -2.0);
+   h.render(0.0, |_| view());
+   assert_eq!(h.events().get("log"), "down Right 10 20, up Right 10 20, down Middle 10 20, wheel 0 -2");
+
+   h.events().set("log", "");
+   h.set_cursor(150, 20);
+   h.mouse_up("middle");
+   h.click();
+   h.render(0.0, |_| view());
+   assert_eq!(h.events().get("log"), "");
+}
+
+#[test]
+fn double_click() {
+   let w = common::window();
+   let mut h = w.headless(240, 120);
+   h.set_cursor(10, 20);
+   h.click();
+   h.render(0.0, |_| view());
+   h.click();
+   h.render(0.3, |_| view());
+   assert_eq!(h.events().get("log"), "down Left 10 20, up Left 10 20, down Left 10 20, double Left 10 20, up Left 10 20");
+
+   //too slow
+   h.events().set("log", "");
+   h.click();
+   h.render(2.0, |_| view());
+   h.click();
+   h.render(3.0, |_| view());
+   assert_eq!(h.events().get("log"), "down Left 10 20, up Left 10 20, down Left 10 20, up Left 10 20");
+}
+
+#[test]
+fn drag() {
+   fn view() -> View {
+      fn log(e: &mut Events, d: &Detail, what: &str) {
+         let mouse = d.mouse.clone().expect("mouse");
+         let entry = format!("{} {} {} from {} {} by {} {}", what, mouse.x, mouse.y, mouse.start.0, mouse.start.1, mouse.delta.0, mouse.delta.1);
+         common::log(e, &entry);
+      }
+      let mut v = View::new();
+      v.append(Rectangle::new(100.0, "px", 100.0, "px")
+              .drag_start(|e, d| log(e, d, "start"))
+              .drag_move(|e, d| log(e, d, "move"))
+              .drag_end(|e, d| log(e, d, "end")));
+      //drawn over the first, so it takes drags that start on both
+      v.append(Rectangle::new(50.0, "px", 50.0, "px")
+              .translate_x(80.0, "px")
+              .drag_start(|e, d| log(e, d, "top start")));
+      v
+   }
+   let w = common::window();
+   let mut h = w.headless(240, 120);
+   h.set_cursor(10, 10);
+   h.mouse_down("left");
+   h.render(0.0, |_| view());
+   h.set_cursor(12, 11);
+   h.render(0.0, |_| view());
+   assert_eq!(h.events().get("log"), "");
+
+   h.set_cursor(20, 10);
+   h.render(0.0, |_| view());
+   h.render(0.0, |_| view());
+   h.set_cursor(200, 50);
+   h.render(0.0, |_| view());
+   h.mouse_up("left");
+   h.render(0.0, |_| view());
+   assert_eq!(h.events().get("log"), "start 20 10 from 10 10 by 8 -1, move 200 50 from 10 10 by 180 40, end 200 50 from 10 10 by 0 0");
+
+   h.events().set("log", "");
+   h.set_cursor(90, 10);
+   h.mouse_down("left");
+   h.render(0.0, |_| view());
+   h.set_cursor(120, 10);
+   h.render(0.0, |_| view());
+   h.mouse_up("left");
+   h.render(0.0, |_| view());
+   assert_eq!(h.events().get("log"), "top start 120 10 from 90 10 by 30 0");
+}
+
+#[test]
+fn drag_to_reorder() {
+   fn view(e: &mut Events) -> View {
+      fn card(key: &str) -> Component {
+         let (start, moved, end) = (format!("{} start", key), format!("{} move", key), format!("{} end", key));
+         Rectangle::new(50.0, "px", 50.0, "px")
+                  .hoverable(key)
+                  .drag_start(move |e, _| {
+                     common::log(e, &start);
+                     //the dragged card goes to the front of the View
+                     let order = e.get("order");
+                     e.set("order", if order == "ba" { "ab" } else { "ba" });
+                  })
+                  .drag_move(move |e, _| common::log(e, &moved))
+                  .drag_end(move |e, _| common::log(e, &end))
+      }
+      let order = e.get("order");
+      let mut v = View::new();
+      for key in if order == "ba" { vec!["b", "a"] } else { vec!["a", "b"] } {
+         //the later card is drawn on top, so each is placed by its key rather than its place in the View
+         v.append(card(key).translate_x(if key == "a" { 0.0 } else { 100.0 }, "px"));
+      }
+      v
+   }
+   let w = common::window();
+   let mut h = w.headless(240, 120);
+   h.set_cursor(110, 10);
+   h.mouse_down("left");
+   h.render(0.0, view);
+   h.set_cursor(130, 10);
+   h.render(0.0, view);
+   assert_eq!(h.events().get("order"), "ba");
+   h.set_cursor(150, 10);
+   h.render(0.0, view);
+   h.mouse_up("left");
+   h.render(0.0, view);
+   assert_eq!(h.events().get("log"), "b start, b move, b end");
+}
+
+#[test]
+fn enter_and_leave() {
+   fn view(e: &mut Events) -> View {
+      let mut v = View::new();
+      //moves when hovered, so it stays told apart by its key rather than its place in the View
+      if e.is_hovered("card") {
+         v.append(Rectangle::new(10.0, "px", 10.0, "px"));
+      }
+      v.append(Rectangle::new(100.0, "px", 100.0, "px")
+              .hoverable("card")
+              .mouse_enter(|e, d| log(e, d, "enter"))
+              .mouse_leave(|e, d| log(e, d, "leave")));
+      v
+   }
+   let w = common::window();
+   let mut h = w.headless(240, 120);
+   h.set_cursor(150, 20);
+   h.render(0.0, view);
+   assert!(!h.events().is_hovered("card"));
+
+   h.set_cursor(10, 20);
+   h.render(0.0, view);
+   assert!(h.events().is_hovered("card"));
+   h.set_cursor(20, 30);
+   h.render(0.0, view);
+   h.set_cursor(150, 20);
+   h.render(0.0, view);
+   assert!(!h.events().is_hovered("card"));
+   assert_eq!(h.events().get("log"), "enter 0 0, leave 0 0");
+}
@@ -0,0 +1,593 @@
+extern crate Lattice;
+mod common;
+use Lattice::view::{View, Group, Image, Text, RichText, TextSpan, TextInput, Rectangle, Length, ViewUnit, AlignUnit};
+use Lattice::events::{Editing};
+use Lattice::snapshot::{Snapshot};
+
+#[test]
+fn basic() {
+   Snapshot::new(320, 240).check(&common::example_window(), "basic", &[0.0], |_| {
+      let mut v = View::new();
+      v.append(Image::new("assets/background.png")
+                 .height(100.0, "%")
+                 .width(100.0, "%"));
+      v.append(Image::new("assets/handcloth.png")
+                 .height(20.0, "%")
+                 .width(20.0, "%"));
+      v.append(Image::new("assets/handcloth.png")
+                 .height(40.0, "%")
+                 .width(40.0, "%")
+                 .translate_x(50.0, "%")
+                 .translate_y(50.0, "%")
+                 .cog(0.5, 0.5)
+                 .rotate(40.0, "degree"));
+      v
+   }).unwrap();
+}
+
+#[test]
+fn conditional() {
+   Snapshot::new(320, 240).check(&common::example_window(), "conditional", &[0.0], |_| {
+      let mut v = View::new();
+      v.append(Rectangle::new(100.0, "%", 100.0, "%")
+                 .always(|e|{ e.set("a","b") }));
+      v.append(Image::new("assets/handcloth.png")
+                 .condition("a", "b")
+                 .translate_x(50.0, "%")
+                 .height(20.0, "%")
+                 .width(20.0, "%"));
+      v.append(Image::new("assets/handcloth.png")
+                 .condition("a", "c")
+                 .translate_y(50.0, "%")
+                 .height(20.0, "%")
+                 .width(20.0, "%"));
+      v
+   }).unwrap();
+}
+
+#[test]
+fn group() {
+   Snapshot::new(320, 240).check(&common::example_window(), "group", &[0.0], |_| {
+      let mut v = View::new();
+      for &(x, y, angle) in [(10.0, 10.0, 0.0), (60.0, 60.0, 20.0)].iter() {
+         v.append(Group::new()
+                    .width(40.0, "%")
+                    .height(30.0, "%")
+                    .border([1.0, 1.0, 1.0, 1.0], 2.0, "px")
+                    .cog(0.5, 0.5)
+                    .rotate(angle, "degree")
+                    .translate_x(x + 20.0, "%")
+                    .translate_y(y + 15.0, "%")
+                    .append(Rectangle::new(100.0, "%", 100.0, "%")
+                            .color([0.2, 0.2, 0.4, 1.0]))
+                    .append(Text::new("assets/Macondo-Regular.ttf", "panel")
+                            .width(100.0, "%")
+                            .align("center"))
+                    .append(Rectangle::new(50.0, "%", 50.0, "%")
+                            .color([0.4, 0.4, 1.0, 1.0])
+                            .translate_x(0.0, "=")
+                            .translate_y(0.0, "=")));
+      }
+      v
+   }).unwrap();
+}
+
+#[test]
+fn modifier_stacking() {
+   Snapshot::new(320, 240).check(&common::example_window(), "modifier_stacking", &[0.0], |_| {
+      let mut v = View::new();
+      let fraction = 75.0 / 10.0;
+      let colors = [
+         [1.0, 0.4, 0.4, 1.0],
+         [0.4, 1.0, 0.4, 1.0],
+         [0.4, 0.4, 1.0, 1.0],
+         [1.0, 1.0, 1.0, 1.0]
+      ];
+      for x in 0..10 {
+      for y in 0..10 {
+         v.append(Rectangle::new(fraction, "<%", fraction, "<%")
+                .color( colors[((x+y) % 4) as usize] )
+                .translate_x(50.0, "h%")
+                .translate_x(-37.5, "<%")
+                .translate_y(50.0, "v%")
+                .translate_y(-37.5, "<%")
+                .translate_x(fraction*(x as f64), "<%")
+                .translate_y(fraction*(y as f64), "<%"));
+      }}
+      v
+   }).unwrap();
+}
+
+#[test]
+fn motion() {
+   Snapshot::new(320, 240).check(&common::example_window(), "motion", &[0.5, 1.5, 2.5, 3.5], |events| {
+      let mut v = View::new();
+      let cycle = events.time_elapsed % 4.0;
+      let cycle_x = if cycle<1.0 { cycle } else if cycle<2.0 { 1.0 } else if cycle<3.0 { 1.0-(cycle-2.0) } else { 0.0 };
+      let cycle_y = if cycle<1.0 { 0.0 } else if cycle<2.0 { cycle-1.0 } else if cycle<3.0 { 1.0 } else { 1.0-(cycle-3.0) };
+      v.append(Image::new("assets/handcloth.png")
+                 .height(20.0, "%")
+                 .width(20.0, "%")
+                 .translate_x(20.0 + 30.0*cycle_x, "%")
+                 .translate_y(20.0 + 30.0*cycle_y, "%"));
+      v
+   }).unwrap();
+}
+
+#[test]
+fn text() {
+   Snapshot::new(320, 240).check(&common::example_window(), "text", &[0.0], |_| {
+      let mut v = View::new();
+      v.append(Text::new("assets/Macondo-Regular.ttf", "paragraph justified paragraph justified paragraph justified")
+              .color([0.4, 0.4, 1.0, 1.0])
+              .shadow([-1,-1,1,1],[1.0,1.0,1.0,1.0])
+              .width(45.0, "%")
+              .align("justify")
+              .translate_x(10.0, "px")
+              .translate_y(10.0, "px"));
+      v.append(Text::new("assets/Macondo-Regular.ttf", "paragraph right aligned paragraph right aligned")
+              .color([1.0, 0.4, 0.4, 1.0])
+              .scale(1.5, "em")
+              .width(45.0, "%")
+              .align("right")
+              .translate_x(50.0, "%")
+              .translate_y(30.0, "%"));
+      v.append(Text::new("assets/Macondo-Regular.ttf", "centered")
+              .width(100.0, "%")
+              .align("center")
+              .translate_y(80.0, "%"));
+      v
+   }).unwrap();
+}
+
+#[test]
+fn scalar_units() {
+   Snapshot::new(640, 640).check(&common::example_window(), "scalar_units", &[0.0], |_| {
+      let mut v = View::new();
+      let lines = [("10 em", 10.0, "em"), ("10 %", 10.0, "%"), ("10 h% (horizontal %)", 10.0, "h%"),
+                   ("10 v% (vertical %)", 10.0, "v%"), ("10 <% (lesser percent)", 10.0, "<%"),
+                   ("10 >% (greater percent)", 10.0, ">%"), ("0 px", 0.0, "px"), ("10 px", 10.0, "px"),
+                   ("calc(10% - 2em)", 1.0, "calc(10% - 2em)")];
+      for (i, &(label, scalar, unit)) in lines.iter().enumerate() {
+         v.append(Text::new("assets/Macondo-Regular.ttf", label)
+                 .scale(2.0, "em")
+                 .color([1.0, 1.0, 1.0, 1.0])
+                 .translate_x(scalar, unit)
+                 .translate_y(1.0 + 3.0 * i as f64, "em"));
+      }
+      v
+   }).unwrap();
+}
+
+#[test]
+fn typesafe_units() {
+   Snapshot::new(640, 480).check(&common::example_window(), "typesafe_units", &[0.0], |_| {
+      let mut v = View::new();
+      v.append(Text::new("assets/Macondo-Regular.ttf", "paragraph justified paragraph justified paragraph justified paragraph justified")
+              .color([0.4, 0.4, 1.0, 1.0])
+              .shadow([-3,-3,3,3],[1.0,1.0,1.0,1.0])
+              .scale(2.0, ViewUnit::Em)
+              .width(25.0, ViewUnit::Percent)
+              .align(AlignUnit::Justify)
+              .translate_x(50.0, ViewUnit::Pixel)
+              .translate_y(50.0, ViewUnit::Pixel));
+      v.append(Text::new("assets/Macondo-Regular.ttf", "paragraph right aligned paragraph right aligned")
+              .color([1.0, 0.4, 0.4, 1.0])
+              .scale(3.0, ViewUnit::Em)
+              .width(40.0, ViewUnit::Percent)
+              .align(AlignUnit::Right)
+              .translate_x(50.0, ViewUnit::Percent)
+              .translate_y(30.0, ViewUnit::Percent));
+      v
+   }).unwrap();
+}
+
+#[test]
+fn events() {
+   //the second frame shows the text hovered and clicked
+   Snapshot::new(640, 480).check(&common::example_window(), "events", &[0.0, 0.0], |events| {
+      if events.frame == 1 {
+         events.hovered = vec!["hover text".to_owned()];
+         events.set("text clicked", "true");
+         events.set("hover count", "1");
+      }
+      let mut v = View::new();
+      let lh = events.is_hovered("hover text");
+      let tc = events.get("text clicked") == "true";
+      v.append(Text::new("assets/Macondo-Regular.ttf", "hover text")
+              .shadow((if lh {[-3,-3,3,3]} else {[0,0,0,0]}),
+                      (if lh {[0.8,0.8,0.8,0.8]} else {[0.0, 0.0, 0.0, 0.0]}))
+              .hoverable("hover text")
+              .color([0.4, 0.4, 1.0, 1.0])
+              .scale(2.0, "em")
+              .width(25.0, "%")
+              .translate_x(150.0, "px")
+              .translate_y(150.0, "px"));
+      v.append(Text::new("assets/Macondo-Regular.ttf", format!("hovered {} times", events.get("hover count").parse::<usize>().unwrap_or(0)).as_str())
+              .color([0.8, 0.8, 0.8, 1.0])
+              .translate_x(150.0, "px")
+              .translate_y(220.0, "px"));
+      v.append(Text::new("assets/Macondo-Regular.ttf", "click text")
+              .shadow((if tc {[-3,-3,3,3]} else {[0,0,0,0]}),
+                      (if tc {[0.8,0.8,0.8,1.0]} else {[0.0,0.0,0.0,0.0]}))
+              .clicked(|e| e.set("text clicked", "true"))
+              .color([1.0, 0.4, 0.4, 1.0])
+              .scale(3.0, "em")
+              .width(40.0, "%")
+              .align("right")
+              .translate_x(50.0, "%")
+              .translate_y(30.0, "%"));
+      v
+   }).unwrap();
+}
+
+#[test]
+fn flex() {
+   Snapshot::new(320, 240).check(&common::example_window(), "flex", &[0.0], |_| {
+      let mut v = View::new();
+      v.append(Group::row()
+                 .width(100.0, "%")
+                 .height(25.0, "%")
+                 .gap(4.0, "px")
+                 .justify("space-between")
+                 .align_items("center")
+                 .append(Rectangle::new(40.0, "px", 20.0, "px")
+                         .color([1.0, 0.4, 0.4, 1.0]))
+                 .append(Text::new("assets/Macondo-Regular.ttf", "row"))
+                 .append(Rectangle::new(40.0, "px", 40.0, "px")
+                         .color([0.4, 1.0, 0.4, 1.0])));
+      v.append(Group::column()
+                 .width(30.0, "%")
+                 .height(75.0, "%")
+                 .translate_y(25.0, "%")
+                 .gap(2.0, "px")
+                 .append(Rectangle::new(100.0, "%", 30.0, "px")
+                         .color([0.4, 0.4, 1.0, 1.0]))
+                 .append(Group::new()
+                         .grow(1.0)
+                         .append(Rectangle::new(100.0, "%", 100.0, "%")
+                                 .color([0.2, 0.2, 0.4, 1.0])))
+                 .append(Rectangle::new(50.0, "%", 30.0, "px")
+                         .color([0.4, 0.4, 1.0, 1.0])));
+      v.append(Group::row()
+                 .width(60.0, "%")
+                 .height(20.0, "%")
+                 .translate_x(35.0, "%")
+                 .translate_y(40.0, "%")
+                 .justify("end")
+                 .align_items("end")
+                 .append(Rectangle::new(200.0, "px", 50.0, "%")
+                         .color([1.0, 1.0, 0.4, 1.0]))
+                 .append(Rectangle::new(100.0, "px", 100.0, "%")
+                         .color([1.0, 0.4, 1.0, 1.0])
+                         .shrink(0.0)));
+      v
+   }).unwrap();
+}
+
+#[test]
+fn grid() {
+   Snapshot::new(320, 240).check(&common::example_window(), "grid", &[0.0], |_| {
+      let mut v = View::new();
+      let mut g = Group::grid()
+                 .width(100.0, "%")
+                 .height(100.0, "%")
+                 .gap(4.0, "px")
+                 .columns(&[(60.0, "px"), (1.0, "fr"), (2.0, "fr")])
+                 .rows(&[(40.0, "px")])
+                 .append(Rectangle::new(100.0, "%", 100.0, "%")
+                         .color([1.0, 0.4, 0.4, 1.0])
+                         .span(3, 1))
+                 .append(Rectangle::new(100.0, "%", 100.0, "%")
+                         .color([0.4, 0.4, 1.0, 1.0])
+                         .cell(0, 1)
+                         .span(1, 2));
+      for i in 0..5 {
+         g = g.append(Rectangle::new(50.0, "%", 50.0, "%")
+                      .color([0.4, 1.0, 0.4, 1.0])
+                      .border([1.0, 1.0, 1.0, 1.0], (i % 2 + 1) as f64, "px")
+                      .translate_x(0.0, "=")
+                      .translate_y(0.0, "="));
+      }
+      v.append(g);
+      v
+   }).unwrap();
+}
+
+#[test]
+fn box_model() {
+   Snapshot::new(320, 240).check(&common::example_window(), "box_model", &[0.0], |_| {
+      let mut v = View::new();
+      v.append(Text::new("assets/Macondo-Regular.ttf", "padded text")
+                 .width(50.0, "%")
+                 .height(30.0, "%")
+                 .padding(8.0, "px")
+                 .margin_sides([10.0, 0.0, 0.0, 10.0], "px")
+                 .color([0.2, 0.2, 0.4, 1.0])
+                 .border([1.0, 1.0, 1.0, 1.0], 2.0, "px"));
+      v.append(Group::row()
+                 .width(100.0, "%")
+                 .height(40.0, "%")
+                 .translate_y(50.0, "%")
+                 .padding_sides([0.0, 10.0, 0.0, 10.0], "px")
+                 .append(Rectangle::new(0.0, "px", 100.0, "%")
+                         .color([1.0, 0.4, 0.4, 1.0])
+                         .grow(1.0)
+                         .max_width(60.0, "px")
+                         .margin(6.0, "px"))
+                 .append(Rectangle::new(0.0, "px", 100.0, "%")
+                         .color([0.4, 1.0, 0.4, 1.0])
+                         .grow(1.0)
+                         .max_height(50.0, "%"))
+                 .append(Rectangle::new(10.0, "px", 100.0, "%")
+                         .color([0.4, 0.4, 1.0, 1.0])
+                         .min_width(40.0, "px")));
+      v
+   }).unwrap();
+}
+
+#[test]
+fn calc() {
+   Snapshot::new(320, 240).check(&common::example_window(), "calc", &[0.0], |_| {
+      let mut v = View::new();
+      v.append(Rectangle::new(1.0, "calc(50% - 2em)", 1.0, "calc(100% - 20px)")
+                 .color([1.0, 0.4, 0.4, 1.0])
+                 .translate_x(1.0, "calc(1em + 10px)")
+                 .translate_y(10.0, "px"));
+      v.append(Rectangle::new(1.0, "clamp(1em, 25%, 60px)", 1.0, "max(10%, 30px)")
+                 .color([0.4, 1.0, 0.4, 1.0])
+                 .translate_x(60.0, "%")
+                 .translate_y(10.0, "px"));
+      v.append(Rectangle::new(1.0, Length::new(30.0, "%") - Length::new(1.0, "em"),
+                              1.0, Length::new(20.0, "%").min(Length::new(2.0, "em")))
+                 .color([0.4, 0.4, 1.0, 1.0])
+                 .translate_x(60.0, "%")
+                 .translate_y(1.0, Length::new(100.0, "%") - Length::new(3.0, "em")));
+      v
+   }).unwrap();
+}
+
+#[test]
+fn wrap() {
+   Snapshot::new(320, 240).check(&common::example_window(), "wrap", &[0.0], |_| {
+      let mut v = View::new();
+      v.append(Text::new("assets/Macondo-Regular.ttf", "hyphen\u{AD}ation every\u{AD}where")
+              .width(22.0, "%")
+              .translate_x(5.0, "px"));
+      v.append(Text::new("assets/Macondo-Regular.ttf", "Supercalifragilistic")
+              .width(30.0, "%")
+              .translate_x(50.0, "%"));
+      v.append(Text::new("assets/Macondo-Regular.ttf", "Supercalifragilistic")
+              .wrap("word")
+              .width(30.0, "%")
+              .translate_x(50.0, "%")
+              .translate_y(30.0, "%"));
+      v.append(Text::new("assets/Macondo-Regular.ttf", "no\u{A0}break here")
+              .width(45.0, "%")
+              .translate_x(5.0, "px")
+              .translate_y(60.0, "%"));
+      v
+   }).unwrap();
+}
+
+#[test]
+fn text_runs() {
+   Snapshot::new(320, 240).check(&common::example_window(), "text_runs", &[0.0], |_| {
+      let mut v = View::new();
+      v.append(Text::new("assets/Macondo-Regular.ttf", "AVATAR Wave To")
+              .scale(2.0, "em")
+              .translate_x(5.0, "px"));
+      v.append(Text::new("assets/Macondo-Regular.ttf", "words are spread across the gaps of justified lines but not the last one")
+              .width(60.0, "%")
+              .align("justify")
+              .translate_x(5.0, "px")
+              .translate_y(25.0, "%"));
+      v
+   }).unwrap();
+}
+
+#[test]
+fn rich_text() {
+   Snapshot::new(320, 240).check(&common::example_window(), "rich_text", &[0.0], |_| {
+      let mut v = View::new();
+      v.append(RichText::new("assets/Macondo-Regular.ttf")
+              .width(90.0, "%")
+              .translate_x(5.0, "%")
+              .translate_y(5.0, "%")
+              .append_span(TextSpan::new("Speak to "))
+              .append_span(TextSpan::new("Premadeath")
+                           .color([1.0, 0.8, 0.2, 1.0])
+                           .scale(1.5, "em")
+                           .underline())
+              .append_span(TextSpan::new(" and bring the "))
+              .append_span(TextSpan::new("silver key")
+                           .color([0.4, 0.8, 1.0, 1.0]))
+              .append_span(TextSpan::new(" before "))
+              .append_span(TextSpan::new("noon")
+                           .strikethrough())
+              .append_span(TextSpan::new(" midnight.")));
+      v.append(RichText::new("assets/Macondo-Regular.ttf")
+              .width(90.0, "%")
+              .align("center")
+              .translate_x(5.0, "%")
+              .translate_y(60.0, "%")
+              .append_span(TextSpan::new("small "))
+              .append_span(TextSpan::new("LARGE").scale(2.5, "em"))
+              .append_span(TextSpan::new(" small")));
+      v
+   }).unwrap();
+}
+
+#[test]
+fn overflow() {
+   Snapshot::new(320, 240).check(&common::example_window(), "overflow", &[0.0], |_| {
+      let mut v = View::new();
+      v.append(Text::new("assets/Macondo-Regular.ttf", "a long paragraph that wraps over many lines of text")
+              .max_lines(2)
+              .overflow("ellipsis")
+              .width(40.0, "%")
+              .translate_x(5.0, "px"));
+      v.append(Text::new("assets/Macondo-Regular.ttf", "a long paragraph that wraps over many lines of text")
+              .overflow("clip")
+              .width(40.0, "%")
+              .height(50.0, "px")
+              .translate_x(55.0, "%"));
+      v.append(Text::new("assets/Macondo-Regular.ttf", "/home/user/documents/report-final.pdf")
+              .max_lines(1)
+              .overflow("ellipsis-middle")
+              .width(60.0, "%")
+              .translate_x(5.0, "px")
+              .translate_y(40.0, "%"));
+      v.append(Text::new("assets/Macondo-Regular.ttf", "Supercalifragilistic")
+              .wrap("word")
+              .overflow("ellipsis")
+              .width(30.0, "%")
+              .translate_x(5.0, "px")
+              .translate_y(60.0, "%"));
+      v.append(Rectangle::new(40.0, "%", 2.0, "px")
+              .color([1.0, 0.0, 0.0, 1.0])
+              .translate_x(55.0, "%")
+              .translate_y(50.0, "px"));
+      v
+   }).unwrap();
+}
+
+#[test]
+fn text_spacing() {
+   Snapshot::new(320, 240).check(&common::example_window(), "text_spacing", &[0.0], |_| {
+      let mut v = View::new();
+      v.append(Text::new("assets/Macondo-Regular.ttf", "loosely set lines of spaced text")
+              .line_height(2.0, "em")
+              .letter_spacing(2.0, "px")
+              .word_spacing(0.5, "em")
+              .align("center")
+              .width(45.0, "%")
+              .translate_x(5.0, "px"));
+      v.append(Rectangle::new(45.0, "%", 40.0, "%")
+              .color([0.2, 0.2, 0.4, 1.0])
+              .translate_x(52.0, "%"));
+      v.append(Text::new("assets/Macondo-Regular.ttf", "middle")
+              .vertical_align("middle")
+              .align("center")
+              .width(45.0, "%")
+              .height(40.0, "%")
+              .translate_x(52.0, "%"));
+      v.append(Text::new("assets/Macondo-Regular.ttf", "bottom")
+              .vertical_align("bottom")
+              .align("right")
+              .width(45.0, "%")
+              .height(40.0, "%")
+              .translate_x(52.0, "%"));
+      v.append(Group::row()
+              .translate_y(60.0, "%")
+              .append(Text::new("assets/Macondo-Regular.ttf", "Big").scale(3.0, "em").vertical_align("baseline"))
+              .append(Text::new("assets/Macondo-Regular.ttf", "small").vertical_align("baseline"))
+              .append(Text::new("assets/Macondo-Regular.ttf", "top")));
+      v
+   }).unwrap();
+}
+
+#[test]
+fn bidi() {
+   Snapshot::new(320, 240).check(&common::example_window(), "bidi", &[0.0], |_| {
+      let mut v = View::new();
+      v.append(Text::new("assets/Macondo-Regular.ttf", "Hello, world!")
+              .direction("rtl")
+              .width(100.0, "%"));
+      v.append(Text::new("assets/Macondo-Regular.ttf", "start (ltr)")
+              .align("start")
+              .width(100.0, "%")
+              .translate_y(25.0, "%"));
+      v.append(Text::new("assets/Macondo-Regular.ttf", "start (rtl)")
+              .direction("rtl")
+              .align("start")
+              .width(100.0, "%")
+              .translate_y(40.0, "%"));
+      v.append(Text::new("assets/Macondo-Regular.ttf", "end (ltr)")
+              .align("end")
+              .width(100.0, "%")
+              .translate_y(55.0, "%"));
+      v.append(Text::new("assets/Macondo-Regular.ttf", "end (rtl)")
+              .direction("rtl")
+              .align("end")
+              .width(100.0, "%")
+              .translate_y(70.0, "%"));
+      v
+   }).unwrap();
+}
+
+#[test]
+fn bidi_scripts() {
+   //Hebrew and Arabic on their own, then mixed into either paragraph direction, with numbers and brackets
+   let lines = [("שלום עולם", "rtl"), ("مرحبا بالعالم", "rtl"), ("Hello שלום world", "ltr"),
+                ("שלום (Lattice 2.0) עולם!", "rtl"), ("Lattice (שלום 123) end", "ltr"),
+                ("السعر 42 دولار", "auto"), ("עברית and English", "auto")];
+   Snapshot::new(320, 240).check(&common::rtl_window(), "bidi_scripts", &[0.0], |_| {
+      let mut v = View::new();
+      for (i, &(text, direction)) in lines.iter().enumerate() {
+         v.append(Text::new(common::RTL_FONT, text)
+                 .direction(direction)
+                 .align("start")
+                 .width(100.0, "%")
+                 .translate_y(2.0 + 13.0 * i as f64, "%"));
+      }
+      v
+   }).unwrap();
+}
+
+#[test]
+fn text_input() {
+   Snapshot::new(320, 240).check(&common::example_window(), "text_input", &[0.0], |e| {
+      if e.frame == 0 {
+         e.set("user", "lattice");
+         e.set("password", "secret");
+         e.set("notes", "first line\nsecond line that wraps");
+         let mut editing = Editing::new("user", 7);
+         editing.anchor = 3;
+         e.editing = Some(editing);
+      }
+      let mut v = View::new();
+      v.append(TextInput::new("assets/Macondo-Regular.ttf", "user")
+              .width(60.0, "%")
+              .border([0.15, 0.15, 0.25, 1.0], 1.0, "px")
+              .translate_x(10.0, "px")
+              .translate_y(10.0, "px"));
+      v.append(TextInput::new("assets/Macondo-Regular.ttf", "password")
+              .password('*')
+              .width(60.0, "%")
+              .border([0.15, 0.15, 0.25, 1.0], 1.0, "px")
+              .translate_x(10.0, "px")
+              .translate_y(50.0, "px"));
+      v.append(TextInput::new("assets/Macondo-Regular.ttf", "notes")
+              .multiline()
+              .width(40.0, "%")
+              .padding(4.0, "px")
+              .border([0.15, 0.15, 0.25, 1.0], 1.0, "px")
+              .translate_x(10.0, "px")
+              .translate_y(90.0, "px"));
+      v
+   }).unwrap();
+}
+
+#[test]
+fn focus_ring() {
+   Snapshot::new(320, 240).check(&common::example_window(), "focus_ring", &[0.0], |e| {
+      if e.frame == 0 {
+         e.focus = Some("save".to_owned());
+      }
+      let mut v = View::new();
+      v.append(Rectangle::new(100.0, "px", 40.0, "px")
+              .color([0.3, 0.5, 0.8, 1.0])
+              .focusable("save")
+              .focus_ring([1.0, 0.8, 0.2, 1.0], 3.0, "px")
+              .translate_x(40.0, "px")
+              .translate_y(100.0, "px"));
+      v.append(Rectangle::new(100.0, "px", 40.0, "px")
+              .color([0.3, 0.5, 0.8, 1.0])
+              .focusable("cancel")
+              .focus_ring([1.0, 0.8, 0.2, 1.0], 3.0, "px")
+              .translate_x(180.0, "px")
+              .translate_y(100.0, "px"));
+      v
+   }).unwrap();
+}
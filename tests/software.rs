@@ -0,0 +1,20 @@
+extern crate Lattice;
+mod common;
+use Lattice::view::{View, Image, Rectangle};
+
+#[test]
+fn missing_image_draws_nothing() {
+   let w = common::window();
+   let mut h = w.headless(40, 30);
+   let frame = h.render(0.0, |_| {
+      let mut v = View::new();
+      v.append(Image::new("assets/missing.png")
+              .width(100.0, "%")
+              .height(100.0, "%"));
+      v.append(Rectangle::new(10.0, "px", 10.0, "px")
+              .color([1.0, 0.0, 0.0, 1.0]));
+      v
+   });
+   assert_eq!(frame.get_pixel(5, 5).data, [255, 0, 0, 255]);
+   assert_eq!(frame.get_pixel(20, 20).data, [0, 0, 0, 255]);
+}
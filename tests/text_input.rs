@@ -0,0 +1,113 @@
+extern crate Lattice;
+mod common;
+use Lattice::view::{View, TextInput};
+
+fn view(multiline: bool) -> View {
+   let mut v = View::new();
+   let input = TextInput::new("assets/Macondo-Regular.ttf", "name")
+              .width(200.0, "px")
+              .translate_x(10.0, "px")
+              .translate_y(10.0, "px");
+   v.append(if multiline { input.multiline() } else { input });
+   v
+}
+
+#[test]
+fn typing_and_navigation() {
+   let w = common::window();
+   let mut h = w.headless(240, 120);
+   h.set_cursor(15, 20);
+   h.click();
+   h.render(0.0, |_| view(false));
+   assert_eq!(h.events().editing.as_ref().map(|e| e.key.as_str()), Some("name"));
+
+   h.type_text("hello");
+   h.press_key("Left");
+   h.press_key("Left");
+   h.press_key("Backspace");
+   h.type_text("X");
+   h.render(0.0, |_| view(false));
+   assert_eq!(h.events().get("name"), "heXlo");
+
+   h.press_key("Home");
+   h.press_key("Delete");
+   h.press_key("End");
+   h.press_key("Shift+Left");
+   h.press_key("Shift+Left");
+   h.type_text("!");
+   h.press_key("Return");
+   h.render(0.0, |_| view(false));
+   assert_eq!(h.events().get("name"), "eX!");
+
+   h.press_key("Ctrl+A");
+   h.press_key("Backspace");
+   h.render(0.0, |_| view(false));
+   assert_eq!(h.events().get("name"), "");
+}
+
+#[test]
+fn multiline_lines() {
+   let w = common::window();
+   let mut h = w.headless(240, 120);
+   h.set_cursor(15, 20);
+   h.click();
+   h.type_text("one");
+   h.press_key("Return");
+   h.type_text("two");
+   h.press_key("Up");
+   h.type_text("+");
+   h.render(0.0, |_| view(true));
+   assert_eq!(h.events().get("name"), "one+\ntwo");
+   h.press_key("Down");
+   h.press_key("Home");
+   h.type_text("-");
+   h.render(0.0, |_| view(true));
+   assert_eq!(h.events().get("name"), "one+\n-two");
+}
+
+#[test]
+fn composition_and_blur() {
+   let w = common::window();
+   let mut h = w.headless(240, 120);
+   h.set_cursor(15, 20);
+   h.click();
+   h.compose("ni", 2);
+   h.render(0.0, |_| view(false));
+   assert_eq!(h.events().get("name"), "");
+   assert_eq!(h.events().editing.as_ref().and_then(|e| e.composition.clone()), Some(("ni".to_owned(), 2)));
+
+   h.type_text("\u{4F60}");
+   h.render(0.0, |_| view(false));
+   assert_eq!(h.events().get("name"), "\u{4F60}");
+   assert_eq!(h.events().editing.as_ref().and_then(|e| e.composition.clone()), None);
+
+   h.set_cursor(15, 100);
+   h.click();
+   h.type_text("ignored");
+   h.render(0.0, |_| view(false));
+   assert!(h.events().editing.is_none());
+   assert_eq!(h.events().get("name"), "\u{4F60}");
+}
+
+#[test]
+fn right_to_left_caret() {
+   let w = common::window();
+   let mut h = w.headless(240, 120);
+   h.set_cursor(15, 20);
+   h.click();
+   h.type_text("אבג");
+   h.render(0.0, |_| view(false));
+
+   //right-to-left text starts on the right, so its end is at the left edge
+   h.set_cursor(11, 20);
+   h.click();
+   h.type_text("X");
+   h.render(0.0, |_| view(false));
+   assert_eq!(h.events().get("name"), "אבגX");
+
+   h.set_cursor(200, 20);
+   h.click();
+   h.type_text("Y");
+   h.render(0.0, |_| view(false));
+   assert_eq!(h.events().get("name"), "YאבגX");
+}